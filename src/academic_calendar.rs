@@ -0,0 +1,237 @@
+//! The academic calendar: lecture periods, holidays and exam periods of each semester.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::File,
+    io::{self, BufReader, Read},
+};
+
+use serde::{Deserialize, Serialize};
+use time::{Date, Weekday};
+
+use crate::module::{appointment::Appointment, semester::Semester};
+
+/// The extension used for academic calendar files.
+pub const EXTENSION: &str = "ron";
+
+/// A named period of days, such as a holiday. Both ends are included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Period {
+    /// Name shown to the user, such as "Christmas".
+    #[serde(default)]
+    pub name: String,
+    #[allow(missing_docs)]
+    pub start: Date,
+    #[allow(missing_docs)]
+    pub end: Date,
+}
+
+impl Period {
+    /// Indicates whether the date is in the period.
+    pub fn contains(&self, date: Date) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// The dates of a semester.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SemesterDates {
+    /// First day of lectures.
+    pub lecture_start: Date,
+    /// Last day of lectures.
+    pub lecture_end: Date,
+    /// Days without lectures during the lecture period, such as the Christmas break.
+    #[serde(default)]
+    pub holidays: Vec<Period>,
+    /// Periods during which the exams take place.
+    #[serde(default)]
+    pub exam_periods: Vec<Period>,
+}
+
+/// The academic calendar of a university, keyed by semester.
+///
+/// Calendars are written as [RON](ron) so that they can be written by hand and shared.
+/// The semesters that are not in the calendar have their [usual lecture period](Semester::lecture_start)
+/// and no holidays or exam periods.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::{academic_calendar::AcademicCalendar, module::semester::Semester};
+/// use time::{Date, Month, Weekday};
+///
+/// let calendar = AcademicCalendar::from_reader(r#"
+/// AcademicCalendar(
+///     semesters: {
+///         (year: 2025, winter: true): (
+///             lecture_start: "2025-10-13",
+///             lecture_end: "2026-02-06",
+///             holidays: [
+///                 (name: "Christmas", start: "2025-12-22", end: "2026-01-06"),
+///             ],
+///             exam_periods: [
+///                 (name: "Exams", start: "2026-02-09", end: "2026-03-07"),
+///             ],
+///         ),
+///     },
+/// )
+/// "#.as_bytes()).unwrap();
+///
+/// let date = |year, month, day| Date::from_calendar_date(year, month, day).unwrap();
+/// assert_eq!(calendar.holiday(date(2025, Month::December, 24)).unwrap().name, "Christmas");
+/// assert!(calendar.exam_period(date(2026, Month::February, 10)).is_some());
+///
+/// // A weekly course on Wednesdays has 15 dates, without the two Wednesdays of the Christmas break.
+/// let dates = calendar.weekly_dates(&Semester::new(2025, true), Weekday::Wednesday);
+/// assert_eq!(dates.len(), 15);
+/// assert_eq!(dates[0], date(2025, Month::October, 15));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AcademicCalendar {
+    #[allow(missing_docs)]
+    pub semesters: BTreeMap<Semester, SemesterDates>,
+}
+
+/// Error returned when an [AcademicCalendar] can't be read.
+#[derive(Debug)]
+pub enum CalendarError {
+    /// The file could not be opened or read.
+    Io(io::Error),
+    /// The content of the file is not a valid calendar.
+    Parse(ron::error::SpannedError),
+    /// A period of a semester ends before it starts.
+    InvertedPeriod {
+        #[allow(missing_docs)]
+        semester: Semester,
+        /// Name of the period.
+        period: String,
+    },
+}
+
+impl Display for CalendarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalendarError::Io(err) => write!(f, "Can't access the file: {err}"),
+            CalendarError::Parse(err) => write!(
+                f,
+                "The file is not a valid academic calendar, at line {}, column {}: {}",
+                err.position.line, err.position.col, err.code
+            ),
+            CalendarError::InvertedPeriod { semester, period } => write!(
+                f,
+                "The period '{period}' of the {semester} ends before it starts"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {}
+
+impl From<io::Error> for CalendarError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl AcademicCalendar {
+    /// Reads a calendar and checks that its periods end after they start.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, CalendarError> {
+        let calendar: Self = ron::de::from_reader(reader).map_err(CalendarError::Parse)?;
+        calendar.validate()?;
+        Ok(calendar)
+    }
+
+    /// Opens the calendar file at the given path.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, CalendarError> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    fn validate(&self) -> Result<(), CalendarError> {
+        for (semester, dates) in &self.semesters {
+            let inverted = |period: &str| CalendarError::InvertedPeriod {
+                semester: semester.clone(),
+                period: period.to_string(),
+            };
+            if dates.lecture_start > dates.lecture_end {
+                return Err(inverted("Lecture period"));
+            }
+            for period in dates.holidays.iter().chain(&dates.exam_periods) {
+                if period.start > period.end {
+                    return Err(inverted(&period.name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// First and last day of lectures of a semester.
+    pub fn lecture_period(&self, semester: &Semester) -> (Date, Date) {
+        match self.semesters.get(semester) {
+            Some(dates) => (dates.lecture_start, dates.lecture_end),
+            None => (semester.lecture_start(), semester.lecture_end()),
+        }
+    }
+
+    /// The exam periods of a semester.
+    pub fn exam_periods(&self, semester: &Semester) -> &[Period] {
+        self.semesters
+            .get(semester)
+            .map(|dates| dates.exam_periods.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The holiday during which the date falls, if any.
+    pub fn holiday(&self, date: Date) -> Option<&Period> {
+        self.semesters
+            .values()
+            .flat_map(|dates| &dates.holidays)
+            .find(|holiday| holiday.contains(date))
+    }
+
+    /// The exam period during which the date falls, if any.
+    pub fn exam_period(&self, date: Date) -> Option<&Period> {
+        self.semesters
+            .values()
+            .flat_map(|dates| &dates.exam_periods)
+            .find(|period| period.contains(date))
+    }
+
+    /// The holiday during which the appointment starts, if any.
+    pub fn on_holiday(&self, appointment: &Appointment) -> Option<&Period> {
+        self.holiday(appointment.get_start().date())
+    }
+
+    /// The dates on which a course given every week on the given weekday takes place:
+    /// every such day of the lecture period of the semester, except the holidays.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::{academic_calendar::AcademicCalendar, module::semester::Semester};
+    /// use time::{Date, Month, Weekday};
+    ///
+    /// // Without holidays, during the usual lecture period from 15 April to 19 July.
+    /// let calendar = AcademicCalendar::default();
+    /// let dates = calendar.weekly_dates(&Semester::new(2024, false), Weekday::Wednesday);
+    ///
+    /// assert_eq!(dates.len(), 14);
+    /// assert_eq!(dates[0], Date::from_calendar_date(2024, Month::April, 17).unwrap());
+    /// assert_eq!(dates[13], Date::from_calendar_date(2024, Month::July, 17).unwrap());
+    /// ```
+    pub fn weekly_dates(&self, semester: &Semester, weekday: Weekday) -> Vec<Date> {
+        let (start, end) = self.lecture_period(semester);
+        let mut date = start
+            .previous_day()
+            .map_or(start, |day| day.next_occurrence(weekday));
+        let mut dates = Vec::new();
+        while date <= end {
+            if self.holiday(date).is_none() {
+                dates.push(date)
+            }
+            date += time::Duration::weeks(1);
+        }
+        dates
+    }
+}
@@ -1,22 +1,40 @@
+use std::{io, path::PathBuf};
+
 use iced::advanced;
+use iced::futures::channel::mpsc;
 use iced::advanced::text::highlighter::PlainText;
-use iced::widget::{TextEditor, TextInput, text_editor, text_input};
+use iced::widget::{
+    TextEditor, TextInput, button, center, column, container::background, mouse_area, opaque,
+    row, stack, text_editor, text_input,
+};
 use iced::window::Settings;
-use iced::{Color, Theme};
+use iced::{Color, Length::Fill, Theme, keyboard};
 use iced::{
     Element, Font, Task,
     widget::{Text, container, text, text::IntoFragment},
 };
 use iced_aw::iced_fonts::REQUIRED_FONT_BYTES;
+use iced_aw::menu::{Item, Menu, MenuBar};
 use iced_fonts::NERD_FONT_BYTES;
+use tum_module_picker::academic_calendar::{self, AcademicCalendar, CalendarError};
+use tum_module_picker::ics::{self, Calendar};
+use tum_module_picker::module::Module;
+use tum_module_picker::project::{self, Project};
+use tum_module_picker::schedule::GroupSelection;
 use tum_module_picker::storage_tree::StorageTree;
+use tum_module_picker::study_plan::StudyPlan;
 use tum_module_picker::window_stack::{Window, WindowStack};
 use tum_module_picker::window_stack_deamon;
 
 use crate::module_tree::ModuleTree;
+use crate::semester_plan::{SemesterPlan, SemesterPlanEvent};
+use crate::timetable::{Timetable, TimetableEvent};
 
 mod module_display;
 mod module_tree;
+mod module_update;
+mod semester_plan;
+mod timetable;
 
 pub const PADDING: u16 = 10;
 pub const MENU_OFFSET: f32 = 20.;
@@ -24,31 +42,369 @@ pub const MENU_OFFSET: f32 = 20.;
 #[derive(Debug)]
 struct App {
     module_tree: ModuleTree,
+    /// Lecture periods, holidays and exam periods shown in the timetable and study plan.
+    academic_calendar: AcademicCalendar,
+
+    /// File in which the project is saved, if any.
+    file: Option<PathBuf>,
+    /// Indicates whether there are unsaved changes.
+    dirty: bool,
+    file_prompt: Option<FilePrompt>,
+    /// Action waiting for the user to accept that the unsaved changes are lost.
+    discard_prompt: Option<Replace>,
+}
+
+/// Overlay asking for the path of a project file.
+#[derive(Debug)]
+struct FilePrompt {
+    action: FileAction,
+    path: String,
+    error: Option<String>,
+}
+
+/// An action replacing the current project, which asks first if there are unsaved changes.
+#[derive(Debug, Clone, Copy)]
+enum Replace {
+    New,
+    Open,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FileAction {
+    Open,
+    SaveAs,
+    ExportCalendar,
+    LoadAcademicCalendar,
 }
 
 #[derive(Debug, Clone)]
 enum Message {
     ModuleTree(module_tree::Message),
+
+    New,
+    Open,
+    Save,
+    SaveAs,
+    ExportCalendar,
+    LoadAcademicCalendar,
+
+    OpenTimetable,
+    OpenStudyPlan,
+
+    Undo,
+    Redo,
+
+    EditPath(String),
+    SubmitPath,
+    ClosePrompt,
+
+    /// Replaces the project, discarding the unsaved changes.
+    Replace(Replace),
+    CloseDiscardPrompt,
 }
 
 impl App {
     fn new() -> (Self, Task<Message>) {
-        let module_tree = ModuleTree::new(StorageTree::node("Aerospace".into(), Vec::new()));
-        (Self { module_tree }, Task::none())
+        (
+            Self {
+                module_tree: ModuleTree::new(new_tree(), StudyPlan::new(), GroupSelection::new()),
+                academic_calendar: AcademicCalendar::default(),
+                file: None,
+                dirty: false,
+                file_prompt: None,
+                discard_prompt: None,
+            },
+            Task::none(),
+        )
     }
 
     fn update(&mut self, message: Message, popup_maker: tum_module_picker::window_stack::PopupMaker) -> Task<Message> {
         match message {
+            Message::Undo => return Task::done(Message::ModuleTree(module_tree::Message::Undo)),
+            Message::Redo => return Task::done(Message::ModuleTree(module_tree::Message::Redo)),
             Message::ModuleTree(message) => {
-                self.module_tree.update(message, popup_maker).map(Message::ModuleTree)
+                self.dirty |= self.module_tree.is_edit(&message);
+                return self.module_tree.update(message, popup_maker).map(Message::ModuleTree);
             }
+            Message::New | Message::Open => {
+                let replace = match message {
+                    Message::New => Replace::New,
+                    _ => Replace::Open,
+                };
+                if self.dirty {
+                    self.discard_prompt = Some(replace)
+                } else {
+                    return Task::done(Message::Replace(replace));
+                }
+            }
+            Message::Replace(replace) => {
+                self.discard_prompt = None;
+                match replace {
+                    Replace::New => {
+                        self.module_tree =
+                            ModuleTree::new(new_tree(), StudyPlan::new(), GroupSelection::new());
+                        self.academic_calendar = AcademicCalendar::default();
+                        self.file = None;
+                        self.dirty = false;
+                    }
+                    Replace::Open => self.prompt(FileAction::Open, None),
+                }
+            }
+            Message::CloseDiscardPrompt => self.discard_prompt = None,
+            Message::Save => match self.file.clone() {
+                Some(path) => {
+                    if let Err(err) = self.save(path) {
+                        self.prompt(FileAction::SaveAs, Some(err.to_string()))
+                    }
+                }
+                None => self.prompt(FileAction::SaveAs, None),
+            },
+            Message::SaveAs => self.prompt(FileAction::SaveAs, None),
+            Message::ExportCalendar => self.prompt(FileAction::ExportCalendar, None),
+            Message::LoadAcademicCalendar => self.prompt(FileAction::LoadAcademicCalendar, None),
+            Message::OpenTimetable => {
+                let (tx, rx) = mpsc::unbounded();
+                return Task::batch(vec![
+                    popup_maker
+                        .popup(
+                            Timetable::new(
+                                self.module_tree.planned_modules(),
+                                self.module_tree.get_groups().clone(),
+                                self.academic_calendar.clone(),
+                                tx,
+                            ),
+                            Settings::default(),
+                        )
+                        .discard(),
+                    Task::run(rx, |event| {
+                        Message::ModuleTree(match event {
+                            TimetableEvent::Open(key) => module_tree::Message::OpenModule(key),
+                            TimetableEvent::SelectGroups(groups) => {
+                                module_tree::Message::SelectGroups(groups)
+                            }
+                        })
+                    }),
+                ]);
+            }
+            Message::OpenStudyPlan => {
+                let (tx, rx) = mpsc::unbounded();
+                let modules = self
+                    .module_tree
+                    .planned_modules()
+                    .into_iter()
+                    .map(|(key, module, _)| (key, module))
+                    .collect();
+                return Task::batch(vec![
+                    popup_maker
+                        .popup(
+                            SemesterPlan::new(
+                                modules,
+                                self.module_tree.get_planned().clone(),
+                                self.academic_calendar.clone(),
+                                tx,
+                            ),
+                            Settings::default(),
+                        )
+                        .discard(),
+                    Task::run(rx, |event| {
+                        Message::ModuleTree(match event {
+                            SemesterPlanEvent::Open(key) => module_tree::Message::OpenModule(key),
+                            SemesterPlanEvent::Assign(id, semester) => {
+                                module_tree::Message::Assign(id, semester)
+                            }
+                        })
+                    }),
+                ]);
+            }
+            Message::EditPath(path) => {
+                if let Some(prompt) = &mut self.file_prompt {
+                    prompt.path = path
+                }
+            }
+            Message::SubmitPath => {
+                if let Some(prompt) = self.file_prompt.take() {
+                    let path = PathBuf::from(&prompt.path);
+                    let result = match prompt.action {
+                        FileAction::Open => self.open(path).map_err(|err| err.to_string()),
+                        FileAction::SaveAs => self.save(path).map_err(|err| err.to_string()),
+                        FileAction::ExportCalendar => self
+                            .export_calendar(path)
+                            .map_err(|err| format!("Can't write the calendar: {err}")),
+                        FileAction::LoadAcademicCalendar => self
+                            .load_academic_calendar(path)
+                            .map_err(|err| err.to_string()),
+                    };
+                    if let Err(err) = result {
+                        self.file_prompt = Some(FilePrompt {
+                            error: Some(err),
+                            ..prompt
+                        })
+                    }
+                }
+            }
+            Message::ClosePrompt => self.file_prompt = None,
+        }
+        Task::none()
+    }
+
+    /// Shows the [FilePrompt] for the given action.
+    fn prompt(&mut self, action: FileAction, error: Option<String>) {
+        let path = self
+            .file
+            .as_ref()
+            .map(|path| match action {
+                FileAction::ExportCalendar => path.with_extension(ics::EXTENSION),
+                FileAction::Open | FileAction::SaveAs => path.clone(),
+                FileAction::LoadAcademicCalendar => path.with_file_name("calendar.ron"),
+            })
+            .map(|path| path.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.file_prompt = Some(FilePrompt {
+            action,
+            path,
+            error,
+        })
+    }
+
+    /// Replaces the current project by the one saved at the given path.
+    fn open(&mut self, path: PathBuf) -> Result<(), project::Error> {
+        let project = Project::load(&path)?;
+        self.module_tree = ModuleTree::new(project.tree, project.planned, project.groups);
+        self.academic_calendar = project.calendar;
+        self.file = Some(path);
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Saves the current project at the given path.
+    fn save(&mut self, path: PathBuf) -> Result<(), project::Error> {
+        let mut project = Project::new(self.module_tree.to_storage_tree());
+        project.planned = self.module_tree.get_planned().clone();
+        project.groups = self.module_tree.get_groups().clone();
+        project.calendar = self.academic_calendar.clone();
+        project.save(&path)?;
+        self.file = Some(path);
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Exports the appointments of the chosen groups, exams and registration deadlines
+    /// of the planned modules as a calendar file at the given path.
+    fn export_calendar(&self, path: PathBuf) -> io::Result<()> {
+        let mut calendar = Calendar::new();
+        let groups = self.module_tree.get_groups();
+        for (_, module, _) in self.module_tree.planned_modules() {
+            calendar.add_module(&module, |course, group| groups.is_chosen(&module, course, group));
         }
+        calendar.save(path)
+    }
+
+    /// Replaces the academic calendar by the one saved at the given path.
+    ///
+    /// The windows opened afterwards show it, and it is saved with the project.
+    fn load_academic_calendar(&mut self, path: PathBuf) -> Result<(), CalendarError> {
+        self.academic_calendar = AcademicCalendar::load(path)?;
+        self.dirty = true;
+        Ok(())
     }
 
     fn view(&self) -> Element<'_, Message> {
-        container(self.module_tree.view().map(Message::ModuleTree))
-            .padding(PADDING)
-            .into()
+        let file_menu = Menu::new(vec![
+            Item::new(menu_button("New", Message::New)),
+            Item::new(menu_button("Open...", Message::Open)),
+            Item::new(menu_button("Save", Message::Save)),
+            Item::new(menu_button("Save As...", Message::SaveAs)),
+            Item::new(menu_button("Export Calendar...", Message::ExportCalendar)),
+            Item::new(menu_button(
+                "Load Academic Calendar...",
+                Message::LoadAcademicCalendar,
+            )),
+        ])
+        .max_width(150.)
+        .spacing(0.);
+
+        let edit_menu = Menu::new(vec![
+            Item::new(menu_button("Undo", Message::Undo)),
+            Item::new(menu_button("Redo", Message::Redo)),
+        ])
+        .max_width(150.)
+        .spacing(0.);
+
+        let view_menu = Menu::new(vec![
+            Item::new(menu_button("Timetable...", Message::OpenTimetable)),
+            Item::new(menu_button("Study Plan...", Message::OpenStudyPlan)),
+        ])
+        .max_width(150.)
+        .spacing(0.);
+
+        let menu_bar = MenuBar::new(vec![
+            Item::with_menu(button(text("File")).style(button::text), file_menu),
+            Item::with_menu(button(text("Edit")).style(button::text), edit_menu),
+            Item::with_menu(button(text("View")).style(button::text), view_menu),
+        ]);
+
+        let app = column![
+            menu_bar,
+            container(self.module_tree.view().map(Message::ModuleTree)).padding(PADDING)
+        ];
+
+        if let Some(replace) = self.discard_prompt {
+            let question = match replace {
+                Replace::New => "Create a new project and discard the unsaved changes?",
+                Replace::Open => "Open another project and discard the unsaved changes?",
+            };
+            let confirm = container(
+                column![
+                    text(question),
+                    row![
+                        button(text("Cancel")).on_press(Message::CloseDiscardPrompt),
+                        button(text("Discard"))
+                            .style(button::danger)
+                            .on_press(Message::Replace(replace)),
+                    ]
+                    .spacing(PADDING),
+                ]
+                .spacing(PADDING / 2),
+            )
+            .style(|theme: &iced::Theme| background(theme.palette().background))
+            .padding(PADDING);
+            return modal(app, confirm, Message::CloseDiscardPrompt);
+        }
+
+        match &self.file_prompt {
+            None => app.into(),
+            Some(prompt) => {
+                let (label, extension) = match prompt.action {
+                    FileAction::Open => ("Open project file:", project::EXTENSION),
+                    FileAction::SaveAs => ("Save project as:", project::EXTENSION),
+                    FileAction::ExportCalendar => ("Export calendar as:", ics::EXTENSION),
+                    FileAction::LoadAcademicCalendar => {
+                        ("Load academic calendar:", academic_calendar::EXTENSION)
+                    }
+                };
+                let input = container(
+                    column![
+                        text(label),
+                        transparent_text_input(
+                            &format!("path/to/file.{extension}"),
+                            &prompt.path
+                        )
+                        .on_input(Message::EditPath)
+                        .on_submit(Message::SubmitPath),
+                    ]
+                    .push_maybe(prompt.error.as_ref().map(|err| {
+                        container(text(err))
+                            .style(backgrounded(container::transparent, ERROR_COLOR))
+                    }))
+                    .spacing(PADDING / 2)
+                    .max_width(500),
+                )
+                .style(|theme: &iced::Theme| background(theme.palette().background))
+                .padding(PADDING);
+
+                modal(app, input, Message::ClosePrompt)
+            }
+        }
     }
 }
 
@@ -68,10 +424,29 @@ impl Window for App {
     }
 
     fn title(&self) -> String {
-        "Module Picker".to_string()
+        let file = self
+            .file
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or("Untitled".to_string());
+        let dirty = if self.dirty { "*" } else { "" };
+        format!("Module Picker - {file}{dirty}")
+    }
+
+    fn on_key_press(&self, key: keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+        history_shortcut(&key, modifiers).map(|step| match step {
+            HistoryStep::Undo => Message::Undo,
+            HistoryStep::Redo => Message::Redo,
+        })
     }
 }
 
+/// The tree of a new project.
+fn new_tree() -> StorageTree<String, Module> {
+    StorageTree::node("Modules".into(), Vec::new())
+}
+
 fn main() -> iced::Result {
     window_stack_deamon!()
         .font(NERD_FONT_BYTES)
@@ -132,3 +507,55 @@ where
 
 pub const ERROR_COLOR: Color = Color::from_rgba(1.0, 0., 0., 0.2);
 pub const INACTIVE_COLOR: Color = Color::from_rgba(0.5, 0.5, 0.5, 0.2);
+pub const HIGHLIGHT_COLOR: Color = Color::from_rgba(1.0, 0.8, 0., 0.4);
+
+/// A step in the undo history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryStep {
+    Undo,
+    Redo,
+}
+
+/// The history step asked by a key press: Ctrl+Z undoes and Ctrl+Shift+Z redoes.
+///
+/// On macOS, Cmd is used instead of Ctrl.
+pub fn history_shortcut(key: &keyboard::Key, modifiers: keyboard::Modifiers) -> Option<HistoryStep> {
+    match key.as_ref() {
+        keyboard::Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("z") => {
+            Some(if modifiers.shift() {
+                HistoryStep::Redo
+            } else {
+                HistoryStep::Undo
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Entry of a drop down menu.
+fn menu_button<'a, Message: Clone + 'a>(label: &'a str, message: Message) -> Element<'a, Message> {
+    button(text(label))
+        .on_press(message)
+        .style(button::text)
+        .width(Fill)
+        .into()
+}
+
+/// Displays `content` above `base`, and produces `on_blur` when clicking outside of `content`.
+pub fn modal<'a, Message>(
+    base: impl Into<Element<'a, Message>>,
+    content: impl Into<Element<'a, Message>>,
+    on_blur: Message,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    stack![
+        base.into(),
+        opaque(
+            mouse_area(center(opaque(content)).style(|_| background(INACTIVE_COLOR)))
+                .on_press(on_blur)
+        )
+    ]
+    .into()
+}
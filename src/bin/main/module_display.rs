@@ -32,6 +32,8 @@ mod overview;
 
 mod description;
 
+mod dates;
+
 impl<'a> From<ModuleDisplay<'a>> for Element<'a, Action> {
     fn from(value: ModuleDisplay<'a>) -> Self {
         let content = value.content;
@@ -54,6 +56,13 @@ impl<'a> From<ModuleDisplay<'a>> for Element<'a, Action> {
         let overview = content.overview_content.view(content).map(Action::Overview);
         let description = content.description_content.view(content).map(Action::Description);
 
-        column![banner, horizontal_rule(PADDING), content_column![overview, description]].into()
+        let dates = dates::view(&content.module);
+
+        column![
+            banner,
+            horizontal_rule(PADDING),
+            content_column![overview, description].push_maybe(dates)
+        ]
+        .into()
     }
 }
@@ -0,0 +1,53 @@
+//! The dates of the courses and exams of a module, summarised as recurring series.
+
+use iced::{Element, widget::text};
+use tum_module_picker::module::{Module, appointment::Series, course::DEFAULT_GROUP};
+
+use crate::{bald_text, content_column, module_display::section::section_header};
+
+/// Views the dates of the module, if it has any.
+///
+/// Each course shows one line per [Series] of each of its groups, instead of every appointment.
+pub fn view<'a, Action: 'a>(module: &'a Module) -> Option<Element<'a, Action>> {
+    let mut lines = content_column![];
+    let mut empty = true;
+
+    for course in &module.courses {
+        let mut groups: Vec<_> = course
+            .get_dates()
+            .iter()
+            .filter(|(_, dates)| !dates.is_empty())
+            .collect();
+        if groups.is_empty() {
+            continue;
+        }
+        groups.sort_by_key(|(group, _)| group.as_str());
+        empty = false;
+
+        lines = lines.push(bald_text(course.get_title()));
+        for (group, dates) in groups {
+            let prefix = if group == DEFAULT_GROUP {
+                String::new()
+            } else {
+                format!("{group}: ")
+            };
+            for series in Series::of(dates) {
+                lines = lines.push(text(format!("{prefix}{series}")));
+            }
+        }
+    }
+
+    for exam in &module.exams {
+        if exam.get_dates().is_empty() {
+            continue;
+        }
+        empty = false;
+
+        lines = lines.push(bald_text(format!("Exam: {}", exam.get_title())));
+        for series in Series::of(exam.get_dates()) {
+            lines = lines.push(text(series.to_string()));
+        }
+    }
+
+    (!empty).then(|| content_column![section_header("Dates", false), lines].into())
+}
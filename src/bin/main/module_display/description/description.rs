@@ -8,7 +8,7 @@ use iced::{
     advanced::graphics::futures::MaybeSend,
     widget::{row, text, text_editor},
 };
-use tum_module_picker::{lines::Lines, module::Module, sectioned_lines::get_sections};
+use tum_module_picker::{lines::Lines, module::{Field, Module}, sectioned_lines::get_sections};
 
 use crate::{module_display::{Edit, Resetable}, *};
 
@@ -189,13 +189,13 @@ fn editor_perform(editable: &mut Option<Editable>, action: text_editor::Action)
 
         let mut sections = get_sections(editable.editor.lines(), &|str| {
             use Section::*;
-            match str {
-                "Prerequisites (recommended)" => Some(Prerequisites),
-                "Intended Learning Outcomes" => Some(IntendedLearningOutcomes),
-                "Content" => Some(Content),
-                "Teaching and Learning Methods" => Some(TeachingAndLearningMethods),
-                "Media" => Some(Media),
-                "Reading List" => Some(ReadingList),
+            match Field::from_header(str)? {
+                Field::Prerequisites => Some(Prerequisites),
+                Field::IntendedLearningOutcomes => Some(IntendedLearningOutcomes),
+                Field::Content => Some(Content),
+                Field::TeachingAndLearningMethods => Some(TeachingAndLearningMethods),
+                Field::Media => Some(Media),
+                Field::ReadingList => Some(ReadingList),
                 _ => None,
             }
         });
@@ -4,7 +4,7 @@ use iced::{
     Element, Task,
     widget::{row, text, text_editor},
 };
-use tum_module_picker::{module::Module, sectioned_lines::get_sections};
+use tum_module_picker::{module::{Field, Module}, sectioned_lines::get_sections};
 
 use crate::{
     bald_text, content_column, editable_maker, module_display::{Edit, Resetable}, set_str_field, texter,
@@ -148,15 +148,17 @@ fn editor_perform(editable: &mut Option<Editable>, action: text_editor::Action)
 
         use Section::*;
 
-        let mut sections = get_sections(editable.editor_content.lines(), &|str| match str {
-            "Module Level" => Some(ModuleLevel),
-            "Abbrevation" => Some(Abbreviation),
-            "Subtitle" => Some(Subtitle),
-            "Duration" => Some(Duration),
-            "Occurrence" => Some(Occurence),
-            "Language" => Some(Language),
-            "Related Programs" => Some(RelatedPrograms),
-            _ => None,
+        let mut sections = get_sections(editable.editor_content.lines(), &|str| {
+            match Field::from_header(str)? {
+                Field::ModuleLevel => Some(ModuleLevel),
+                Field::Abbreviation => Some(Abbreviation),
+                Field::Subtitle => Some(Subtitle),
+                Field::Duration => Some(Duration),
+                Field::Occurence => Some(Occurence),
+                Field::Language => Some(Language),
+                Field::RelatedPrograms => Some(RelatedPrograms),
+                _ => None,
+            }
         });
 
         let mut tasks = vec![];
@@ -1,7 +1,7 @@
 //! Study subsection of description
 
 use std::{
-    fmt::Display, ops::Deref, sync::{Arc, LazyLock}
+    fmt::Display, ops::Deref, sync::Arc
 };
 
 use crate::{
@@ -14,8 +14,7 @@ use iced::{
     Element, Task,
     widget::{row, text, text_editor},
 };
-use regex::Regex;
-use tum_module_picker::{lines::Lines, module::Module, sectioned_lines::get_sections};
+use tum_module_picker::{lines::Lines, module::{self, Field, Module}, sectioned_lines::get_sections};
 
 /// Content of the study section
 #[derive(Debug)]
@@ -172,11 +171,13 @@ fn perform_editor(editable: &mut Option<Editable>, action: text_editor::Action)
 
         let mut tasks = vec![];
 
-        let mut sections = get_sections(editable.editor.lines(), &|str| match str {
-            "Description of Achievement and Assessment Methods" => Some(Section::Descr),
-            "Exam retake next semester" => Some(Section::Next),
-            "Exam retake at the end of semester" => Some(Section::End),
-            _ => None,
+        let mut sections = get_sections(editable.editor.lines(), &|str| {
+            match Field::from_header(str)? {
+                Field::DescrOfAchievementAssessmentMethods => Some(Section::Descr),
+                Field::ExamRetakeNextSemester => Some(Section::Next),
+                Field::ExamRetakeEndSemester => Some(Section::End),
+                _ => None,
+            }
         });
 
         while let Some(sec) = sections.next_section() {
@@ -215,18 +216,8 @@ impl Display for ParseError {
     }
 }
 
-static TRUE_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\b\<(?i)(y|yes|true)\b\>").unwrap());
-static FALSE_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"\b\<(?i)(n|no|false)\b\>").unwrap());
 fn parse_bool(str: &str) -> Result<bool, ParseError> {
-    if TRUE_REGEX.is_match(str) {
-        return Ok(true);
-    }
-    if FALSE_REGEX.is_match(str) {
-        return Ok(false);
-    }
-    return Err(ParseError);
+    module::parse_bool(str).ok_or(ParseError)
 }
 
 fn bool_to_string(bool: &bool) -> String {
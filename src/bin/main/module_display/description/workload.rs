@@ -4,7 +4,7 @@ use std::num::ParseIntError;
 
 use iced::{widget::{row, text_editor}, Element, Task, Alignment::Center};
 use time::Duration;
-use tum_module_picker::{module::Module, sectioned_lines::get_sections};
+use tum_module_picker::{module::{Field, Module}, sectioned_lines::get_sections};
 
 use crate::{bald_text, content_column, editable_maker, module_display::{Edit, Resetable}, non_str_texter, set_non_str_field};
 
@@ -121,10 +121,10 @@ fn perform_editor(editable: &mut Option<Editable>, action: text_editor::Action)
 
         let mut sections = get_sections(editable.editor.lines(), &|str| {
             use Sec::*;
-            match str {
-                "Total Hours" => Some(Total),
-                "Contact Hours" => Some(Contact),
-                "Self-study Hours" => Some(SelfStudy),
+            match Field::from_header(str)? {
+                Field::TotalHours => Some(Total),
+                Field::ContactHours => Some(Contact),
+                Field::SelfStudyHours => Some(SelfStudy),
                 _ => None
             }
         });
@@ -4,7 +4,7 @@ use std::ops::Deref;
 use iced::{Task, advanced::graphics::futures::MaybeSend, widget::text_editor};
 use tum_module_picker::{
     borrow_map::BorrowMap,
-    module::semester::Semester,
+    module::{Field, semester::Semester},
     sectioned_lines::get_sections,
 };
 
@@ -50,15 +50,15 @@ pub(super) fn perform<Message: MaybeSend + 'static>(
 
         let mut section_iter = get_sections(editable.overview_content.lines(), &|str| {
             use SideSections::*;
-            match str {
-                "Name" => Some(Name),
-                "Module ID" => Some(Id),
-                "ECTS credits" => Some(ECTS),
-                "Version" => Some(Version),
-                "Valid" => Some(Valid),
-                "Responsible for Module" => Some(Responsible),
-                "Organisation" => Some(Organisation),
-                "Note" => Some(Note),
+            match Field::from_header(str)? {
+                Field::Name => Some(Name),
+                Field::Id => Some(Id),
+                Field::ECTS => Some(ECTS),
+                Field::Version => Some(Version),
+                Field::ValidFrom => Some(Valid),
+                Field::Responsible => Some(Responsible),
+                Field::Organisation => Some(Organisation),
+                Field::Note => Some(Note),
                 _ => None,
             }
         });
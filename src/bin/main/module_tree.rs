@@ -1,39 +1,191 @@
 //! Helpers for the [ModuleTree] widget.
 
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
 use crate::module_display::ModuleDisplay;
+use crate::module_update::ModuleUpdate;
 
 use super::*;
 use iced::{
-    futures::{channel::mpsc, SinkExt}, widget::{
-        button, center, column, container::background, horizontal_rule, mouse_area, opaque,
-        scrollable, stack, text,
+    futures::{channel::mpsc, stream, StreamExt}, widget::{
+        button, center, column, container::background, horizontal_rule, rich_text, scrollable, span, text,
     }, Element, Length::{Fill, Shrink}
 };
 use iced_aw::ContextMenu;
+
 use tum_module_picker::{
-    module::Module,
+    history::History,
+    module::{Change, ECTS, Module, semester::Semester},
+    schedule::GroupSelection,
+    search::{self, Index, Results},
     storage_tree::{
         self, Node, Path,
         column::{Action, Content, MetaKey, NodeState},
     },
+    study_plan::StudyPlan,
     window_stack::PopupMaker,
 };
 
+/// Identifies a module of the tree while the program runs, wherever it is moved.
+///
+/// Windows and the history refer to modules by key, as their paths change when the tree is edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ModuleKey(u64);
+
+impl ModuleKey {
+    /// A key that no other module has.
+    fn new() -> Self {
+        Self(next_key())
+    }
+}
+
+/// Identifies a folder of the tree while the program runs, wherever it is moved.
+///
+/// A new module is added to its folder by key, as the folder can move while the module is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FolderKey(u64);
+
+/// A number that no other key of a module or folder has.
+fn next_key() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A folder of the tree, with its key.
+#[derive(Debug, Clone, PartialEq)]
+struct Folder {
+    key: FolderKey,
+    name: String,
+}
+
+impl Folder {
+    fn new(name: String) -> Self {
+        Self {
+            key: FolderKey(next_key()),
+            name,
+        }
+    }
+}
+
+/// A module of the tree, with its key.
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    key: ModuleKey,
+    module: Module,
+}
+
+impl Entry {
+    fn new(module: Module) -> Self {
+        Self {
+            key: ModuleKey::new(),
+            module,
+        }
+    }
+}
+
+/// A [ModuleWindow] open on a module of the tree.
+#[derive(Debug)]
+struct OpenWindow {
+    id: usize,
+    key: ModuleKey,
+    /// Sends the module to the window when something else changes it, or nothing once it is gone.
+    refresh: mpsc::UnboundedSender<Option<Box<Module>>>,
+}
+
+/// A new id for a [ModuleWindow], unique even among the windows of a previous tree.
+fn window_id() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
 #[derive(Debug)]
 pub struct ModuleTree {
-    content: Content<String, Module>,
+    content: Content<Folder, Entry>,
+    /// Semester in which each module is planned.
+    planned: StudyPlan,
+    /// The group chosen for the courses of the modules.
+    groups: GroupSelection,
+    /// The [FolderTotal] of every sub tree of the content, updated after each edit.
+    totals: StorageTree<FolderTotal, FolderTotal>,
+    /// The previous versions of the project, to undo edits.
+    ///
+    /// The updates of a module sent by its window are grouped by module, so that typing in a field is one step.
+    /// The module windows undo and redo with this history too.
+    history: History<Snapshot, ModuleKey>,
+    /// The module windows, refreshed after each edit.
+    windows: Vec<OpenWindow>,
 
     path: Path,
     overlay: Overlay,
 
+    search_input: String,
+    /// Index of the words of the modules, by key. Updated with each edit of a module.
+    index: Index<ModuleKey>,
+    /// The modules matching the search, if a search is typed. Updated after each edit.
+    search: Option<Results>,
+
     new_folder_name: String,
+    rename_input: String,
+    plan_input: String,
+    plan_error: Option<String>,
     //new_module_content: module_display::Content,
 }
 
+/// What the modules inside a folder add up to.
+#[derive(Debug, Clone, Copy, Default)]
+struct FolderTotal {
+    ects: ECTS,
+    modules: usize,
+}
+
+impl FolderTotal {
+    /// Computes the total of every sub tree of the content.
+    fn of_tree(content: &Content<Folder, Entry>) -> StorageTree<Self, Self> {
+        content.fold_subtrees(
+            &|entry| Self {
+                ects: entry.module.ects,
+                modules: 1,
+            },
+            &|_, children| Self {
+                ects: children.iter().map(|total| total.ects).sum(),
+                modules: children.iter().map(|total| total.modules).sum(),
+            },
+        )
+    }
+}
+
+/// What an edit of the tree can change.
+///
+/// The modules keep their keys, so that windows still find them after undoing.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    tree: StorageTree<Folder, Entry>,
+    planned: StudyPlan,
+    groups: GroupSelection,
+}
+
+impl Snapshot {
+    fn of(content: &Content<Folder, Entry>, planned: &StudyPlan, groups: &GroupSelection) -> Self {
+        Self {
+            tree: content.to_storage_tree(),
+            planned: planned.clone(),
+            groups: groups.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 enum Overlay {
     None,
     Folder,
+    Rename,
+    Move,
+    Plan,
+    /// Asks before deleting a folder.
+    Delete,
     //Module,
 }
 
@@ -42,42 +194,477 @@ pub enum Message {
     ModuleTree(storage_tree::column::Action),
     AddFolder(Path),
     EditAddFolder(String),
-    AddModule(Path, Module),
+    /// Adds a module to the folder with the given key.
+    AddModule(FolderKey, Module),
+    /// The module window with the given id edited its module.
+    EditModule(usize, Module),
+    /// Merges the changes chosen in the window updating the module into its current version.
+    MergeChanges(ModuleKey, Vec<Change>),
+    OpenModule(ModuleKey),
+    WindowClosed(usize),
+    /// Opens the window updating the module from a TUMonline page.
+    UpdatePressed(Path),
+    RenamePressed(Path),
+    EditRename(String),
+    Rename(Path),
+    Duplicate(Path),
+    MovePressed(Path),
+    /// Moves the sub tree at the first path into the folder at the second one.
+    MoveTo(Path, Path),
+    MoveUp(Path),
+    MoveDown(Path),
+    /// Deletes a module, or asks first for a folder.
+    DeletePressed(Path),
+    Delete(Path),
+    PlanPressed(Path),
+    EditPlan(String),
+    Plan(Path),
+    Unplan(Path),
+    /// Plans the module with the given id for a semester.
+    Assign(String, Semester),
+    SelectGroups(GroupSelection),
     NewFolderPressed(Path),
     NewModulePressed(Path),
     OverlayQuit,
+    EditSearch(String),
+    /// Opens the module that best matches the search.
+    SubmitSearch,
+    Undo,
+    Redo,
     //ModuleBuilder(module_display::Action),
 }
 
+impl Message {
+    /// Indicates whether the message modifies the modules or folders, and not only the way they are displayed.
+    pub fn is_edit(&self) -> bool {
+        matches!(
+            self,
+            Message::ModuleTree(Action::Move { .. })
+                | Message::AddFolder(_)
+                | Message::AddModule(_, _)
+                | Message::EditModule(_, _)
+                | Message::MergeChanges(_, _)
+                | Message::Rename(_)
+                | Message::Duplicate(_)
+                | Message::MoveTo(_, _)
+                | Message::MoveUp(_)
+                | Message::MoveDown(_)
+                | Message::Delete(_)
+                | Message::Plan(_)
+                | Message::Unplan(_)
+                | Message::Assign(_, _)
+                | Message::SelectGroups(_)
+                | Message::Undo
+                | Message::Redo
+        )
+    }
+}
+
 impl ModuleTree {
-    pub fn new(tree: StorageTree<String, Module>) -> Self {
+    pub fn new(
+        tree: StorageTree<String, Module>,
+        planned: StudyPlan,
+        groups: GroupSelection,
+    ) -> Self {
+        let content =
+            Content::new(tree.map_ref(&|name| Folder::new(name.clone()), &|module| {
+                Entry::new(module.clone())
+            }));
+        let mut index = Index::new();
+        for entry in content.leaf_iter() {
+            index.insert(entry.key, &entry.module)
+        }
         Self {
-            content: Content::new(tree),
+            index,
+            totals: FolderTotal::of_tree(&content),
+            history: History::new(),
+            windows: Vec::new(),
+            content,
+            planned,
+            groups,
             new_folder_name: "".into(),
+            rename_input: "".into(),
+            plan_input: "".into(),
+            plan_error: None,
             path: Path::default(),
             overlay: Overlay::None,
+            search_input: String::new(),
+            search: None,
             //new_module_content,
         }
     }
 
+    /// Returns a copy of the module tree, as it should be saved.
+    pub fn to_storage_tree(&self) -> StorageTree<String, Module> {
+        self.content
+            .map_ref(&|folder| folder.name.clone(), &|entry| entry.module.clone())
+    }
+
+    /// Gets a reference to the semester in which each module is planned.
+    pub fn get_planned(&self) -> &StudyPlan {
+        &self.planned
+    }
+
+    /// Gets a reference to the group chosen for the courses of the modules.
+    pub fn get_groups(&self) -> &GroupSelection {
+        &self.groups
+    }
+
+    /// Returns a copy of every planned module, with its key and semester.
+    pub fn planned_modules(&self) -> Vec<(ModuleKey, Module, Semester)> {
+        self.content
+            .leaf_iter()
+            .filter_map(|entry| {
+                let semester = self.planned.get(&entry.module.id)?;
+                Some((entry.key, entry.module.clone(), semester.clone()))
+            })
+            .collect()
+    }
+
+    /// Indicates whether the message changes the project, see [Message::is_edit].
+    ///
+    /// Moving the first entry of a folder up or the last one down, adding a module to a deleted
+    /// folder, and undoing or redoing without history, change nothing.
+    pub fn is_edit(&self, message: &Message) -> bool {
+        match message {
+            Message::MoveUp(path) => self.sibling(path, false).is_some(),
+            Message::MoveDown(path) => self.sibling(path, true).is_some(),
+            Message::AddModule(folder, _) => self.folder_path(*folder).is_some(),
+            Message::Undo => self.history.can_undo(),
+            Message::Redo => self.history.can_redo(),
+            message => message.is_edit(),
+        }
+    }
+
+    /// Lists every folder with its path, its key and its full name, such as "Master / Electives".
+    fn folders(&self) -> Vec<(Path, FolderKey, String)> {
+        let mut res = Vec::new();
+        let mut stack = vec![(Path::new(), String::new(), &*self.content)];
+        while let Some((path, prefix, tree)) = stack.pop() {
+            if let StorageTree::Node(node) = tree {
+                let folder = node.get_key();
+                let name = if prefix.is_empty() {
+                    folder.name.clone()
+                } else {
+                    format!("{prefix} / {}", folder.name)
+                };
+                stack.extend(node.get_children().iter().enumerate().rev().map(|(index, child)| {
+                    let mut path = path.clone();
+                    path.push(index);
+                    (path, name.clone(), child)
+                }));
+                res.push((path, folder.key, name));
+            }
+        }
+        res
+    }
+
+    /// The current path of the folder with the given key.
+    fn folder_path(&self, key: FolderKey) -> Option<Path> {
+        self.folders()
+            .into_iter()
+            .find(|(_, folder, _)| *folder == key)
+            .map(|(path, _, _)| path)
+    }
+
+    /// The current version of the project, to be put in the history.
+    fn snapshot(&self) -> Snapshot {
+        Snapshot::of(&self.content, &self.planned, &self.groups)
+    }
+
+    /// Goes back to a version of the project from the history.
+    fn restore(&mut self, snapshot: Snapshot) {
+        // Only the modules that differ between the versions are indexed again.
+        let old: BTreeMap<_, _> = self
+            .content
+            .leaf_iter()
+            .map(|entry| (entry.key, &entry.module))
+            .collect();
+        let new: BTreeMap<_, _> = snapshot
+            .tree
+            .leaf_iter()
+            .map(|entry| (entry.key, &entry.module))
+            .collect();
+        for key in old.keys().filter(|key| !new.contains_key(key)) {
+            self.index.remove(key);
+        }
+        for (key, module) in &new {
+            if old.get(key) != Some(module) {
+                self.index.insert(*key, module)
+            }
+        }
+        self.content.replace_tree(snapshot.tree);
+        self.planned = snapshot.planned;
+        self.groups = snapshot.groups;
+        self.overlay = Overlay::None;
+    }
+
+    fn get_entry(&self, path: &Path) -> Option<&Entry> {
+        match self.content.get(path)? {
+            StorageTree::Leaf(entry) => Some(entry),
+            StorageTree::Node(_) => None,
+        }
+    }
+
+    fn get_module(&self, path: &Path) -> Option<&Module> {
+        self.get_entry(path).map(|entry| &entry.module)
+    }
+
+    /// Finds the module with the given key, and its current path.
+    fn find(&self, key: ModuleKey) -> Option<(Path, &Module)> {
+        self.content
+            .leaf_paths()
+            .into_iter()
+            .find(|(_, entry)| entry.key == key)
+            .map(|(path, entry)| (path, &entry.module))
+    }
+
+    /// The key of the module shown in the window with the given id.
+    fn window_key(&self, id: usize) -> Option<ModuleKey> {
+        let window = self.windows.iter().find(|window| window.id == id)?;
+        Some(window.key)
+    }
+
+    /// Sends their module to the open windows, except the one that made the edit, and closes the
+    /// windows of the modules that no longer exist.
+    fn refresh_windows(&self, except: Option<usize>) {
+        for window in self.windows.iter().filter(|window| Some(window.id) != except) {
+            let module = self.find(window.key).map(|(_, module)| Box::new(module.clone()));
+            let _ = window.refresh.unbounded_send(module);
+        }
+    }
+
+    /// The path of the entry before or after the one at the given path, if its folder has one.
+    fn sibling(&self, path: &Path, after: bool) -> Option<Path> {
+        self.content.get(path)?;
+        let (index, parent) = path.split_last()?;
+        let index = if after { index + 1 } else { index.checked_sub(1)? };
+        let mut sibling = parent.to_vec();
+        sibling.push(index);
+        self.content.get(&sibling).map(|_| sibling)
+    }
+
     pub fn update(&mut self, message: Message, popup: PopupMaker) -> Task<Message> {
+        let edit = self.is_edit(&message);
+        if edit && !matches!(message, Message::Undo | Message::Redo) {
+            let group = match &message {
+                Message::EditModule(id, _) => self.window_key(*id),
+                _ => None,
+            };
+            self.history.record(group, || {
+                Snapshot::of(&self.content, &self.planned, &self.groups)
+            });
+        }
+        let searching = matches!(message, Message::EditSearch(_));
+        // The window that edited its module already shows it.
+        let source = match &message {
+            Message::EditModule(id, _) => Some(*id),
+            _ => None,
+        };
+        let task = self.perform(message, popup);
+        if edit {
+            self.refresh_windows(source);
+            self.totals = FolderTotal::of_tree(&self.content);
+        }
+        if edit || searching {
+            self.search = (!self.search_input.trim().is_empty()).then(|| {
+                let modules: BTreeMap<_, _> = self
+                    .content
+                    .leaf_paths()
+                    .into_iter()
+                    .map(|(path, entry)| (entry.key, (path, &entry.module)))
+                    .collect();
+                Results::of_index(&self.index, &self.search_input, |key| {
+                    modules.get(key).cloned()
+                })
+            });
+        }
+        task
+    }
+
+    fn perform(&mut self, message: Message, popup: PopupMaker) -> Task<Message> {
         match message {
             Message::ModuleTree(action) => self.content.perform(action),
             Message::AddFolder(path) => {
                 let name = std::mem::take(&mut self.new_folder_name);
-                let new_folder =
-                    StorageTree::node(MetaKey::new(name, NodeState::default()), Vec::new());
-                self.content.add(new_folder, &path);
+                let folder = MetaKey::new(Folder::new(name), NodeState::default());
+                self.content.add(StorageTree::node(folder, Vec::new()), &path);
                 return Task::done(Message::ModuleTree(Action::Expand(path)));
             }
             Message::EditAddFolder(text) => self.new_folder_name = text,
-            Message::AddModule(_,_) => (), //self.new_module_content.set_all_edits(false),
+            Message::AddModule(folder, module) => {
+                let Some(path) = self.folder_path(folder) else {
+                    return Task::none();
+                };
+                let leaf = StorageTree::leaf(Entry::new(module));
+                if let Some(StorageTree::Leaf(entry)) = self.content.add(leaf, &path) {
+                    self.index.insert(entry.key, &entry.module);
+                    return Task::done(Message::ModuleTree(Action::Expand(path)));
+                }
+            }
+            Message::EditModule(id, module) => {
+                let key = self.window_key(id);
+                if let Some(entry) = self.content.mut_leaf_iter().find(|e| Some(e.key) == key) {
+                    self.index.insert(entry.key, &module);
+                    entry.module = module
+                }
+            }
+            Message::MergeChanges(key, changes) => {
+                if let Some(entry) = self.content.mut_leaf_iter().find(|entry| entry.key == key) {
+                    entry.module.merge(&changes);
+                    self.index.insert(key, &entry.module)
+                }
+            }
+            Message::OpenModule(key) => {
+                if let Some((_, module)) = self.find(key) {
+                    let (tx, rx) = mpsc::unbounded();
+                    let (refresh, refreshes) = mpsc::unbounded();
+                    let window = ModuleWindow::new(module.clone(), tx, refreshes);
+                    let id = window_id();
+                    self.windows.push(OpenWindow { id, key, refresh });
+                    // The events end when the window is closed.
+                    let events = rx.map(Some).chain(stream::once(async { None }));
+                    return Task::batch(vec![
+                        popup.popup(window, Settings::default()).discard(),
+                        Task::run(events, move |event| match event {
+                            Some(ModuleWindowEvent::Edit(module)) => {
+                                Message::EditModule(id, *module)
+                            }
+                            Some(ModuleWindowEvent::Undo) => Message::Undo,
+                            Some(ModuleWindowEvent::Redo) => Message::Redo,
+                            None => Message::WindowClosed(id),
+                        }),
+                    ]);
+                }
+            }
+            Message::WindowClosed(id) => self.windows.retain(|window| window.id != id),
+            Message::UpdatePressed(path) => {
+                if let Some(Entry { key, module }) = self.get_entry(&path) {
+                    let (tx, rx) = mpsc::unbounded();
+                    let key = *key;
+                    return Task::batch(vec![
+                        popup
+                            .popup(ModuleUpdate::new(module.clone(), tx), Settings::default())
+                            .discard(),
+                        Task::run(rx, move |changes| Message::MergeChanges(key, changes)),
+                    ]);
+                }
+            }
+            Message::RenamePressed(path) => {
+                self.rename_input = match self.content.get(&path) {
+                    Some(StorageTree::Leaf(entry)) => entry.module.name.clone(),
+                    Some(StorageTree::Node(node)) => node.get_key().name.clone(),
+                    None => String::new(),
+                };
+                self.path = path;
+                self.overlay = Overlay::Rename
+            }
+            Message::EditRename(text) => self.rename_input = text,
+            Message::Rename(path) => {
+                let name = std::mem::take(&mut self.rename_input);
+                match self.content.get_mut(&path) {
+                    Some(StorageTree::Leaf(entry)) => {
+                        entry.module.name = name;
+                        self.index.insert(entry.key, &entry.module)
+                    }
+                    Some(StorageTree::Node(node)) => node.get_mut_key().name = name,
+                    None => (),
+                }
+                self.overlay = Overlay::None
+            }
+            Message::Duplicate(path) => {
+                if let (Some((index, parent)), Some(tree)) =
+                    (path.split_last(), self.content.get(&path))
+                {
+                    // The copies are other modules and folders, so they get new keys.
+                    let copy = tree.map_ref(
+                        &|folder| {
+                            MetaKey::new(Folder::new(folder.name.clone()), NodeState::default())
+                        },
+                        &|entry| Entry::new(entry.module.clone()),
+                    );
+                    for entry in copy.leaf_iter() {
+                        self.index.insert(entry.key, &entry.module)
+                    }
+                    let _ = self.content.insert(copy, &parent.to_vec(), index + 1);
+                }
+            }
+            Message::MovePressed(path) => {
+                self.path = path;
+                self.overlay = Overlay::Move
+            }
+            Message::MoveTo(from, to) => {
+                self.content.move_to(&from, &to, usize::MAX);
+                self.overlay = Overlay::None;
+                if let Some(to) = storage_tree::path_after_removal(&to, &from) {
+                    return Task::done(Message::ModuleTree(Action::Expand(to)));
+                }
+            }
+            Message::MoveUp(path) => {
+                if let Some(previous) = self.sibling(&path, false) {
+                    self.content.swap(&path, &previous);
+                }
+            }
+            Message::MoveDown(path) => {
+                if let Some(next) = self.sibling(&path, true) {
+                    self.content.swap(&path, &next);
+                }
+            }
+            Message::DeletePressed(path) => match self.content.get(&path) {
+                Some(StorageTree::Node(_)) => {
+                    self.path = path;
+                    self.overlay = Overlay::Delete
+                }
+                _ => return Task::done(Message::Delete(path)),
+            },
+            Message::Delete(path) => {
+                for entry in self.content.remove(&path).iter().flat_map(StorageTree::leaf_iter) {
+                    self.index.remove(&entry.key);
+                }
+                self.overlay = Overlay::None
+            }
+            Message::PlanPressed(path) => {
+                self.plan_input = self
+                    .get_module(&path)
+                    .and_then(|module| self.planned.get(&module.id))
+                    .map(Semester::to_string)
+                    .unwrap_or_default();
+                self.plan_error = None;
+                self.path = path;
+                self.overlay = Overlay::Plan
+            }
+            Message::EditPlan(text) => self.plan_input = text,
+            Message::Plan(path) => match self.plan_input.parse::<Semester>() {
+                // Plans and group choices are kept by module id, so they need a unique one.
+                Ok(_) if self.get_module(&path).is_some_and(|module| module.id.is_empty()) => {
+                    self.plan_error = Some("Set the id of the module to plan it".to_string())
+                }
+                Ok(semester) => {
+                    if let Some(module) = self.get_module(&path) {
+                        self.planned.assign(module.id.clone(), semester);
+                    }
+                    self.overlay = Overlay::None
+                }
+                Err(err) => self.plan_error = Some(err.to_string()),
+            },
+            Message::Unplan(path) => {
+                if let Some(module) = self.get_module(&path) {
+                    let id = module.id.clone();
+                    self.planned.remove(&id);
+                }
+            }
+            Message::Assign(id, semester) => self.planned.assign(id, semester),
+            Message::SelectGroups(groups) => self.groups.extend(groups),
             Message::NewFolderPressed(path) => {
                 self.path = path;
                 self.overlay = Overlay::Folder
             }
             Message::NewModulePressed(path) => {
                 //self.overlay = Overlay::Module
+                let Some(StorageTree::Node(node)) = self.content.get(&path) else {
+                    return Task::none();
+                };
+                let folder = node.get_key().key;
                 let (tx, rx) = mpsc::channel(1);
                 return Task::batch(vec![popup
                     .popup(NewModulePopup::new(tx), Settings {
@@ -85,28 +672,87 @@ impl ModuleTree {
                         ..Settings::default()
                     })
                     .discard(),
-                    Task::run(rx, move |m| Message::AddModule(path.clone(), m))]);
+                    Task::run(rx, move |m| Message::AddModule(folder, m))]);
             }
             Message::OverlayQuit => self.overlay = Overlay::None,
+            Message::EditSearch(text) => self.search_input = text,
+            Message::SubmitSearch => {
+                if let Some(entry) = self
+                    .search
+                    .as_ref()
+                    .and_then(|results| results.ranking().first())
+                    .and_then(|path| self.get_entry(path))
+                {
+                    return Task::done(Message::OpenModule(entry.key));
+                }
+            }
+            Message::Undo => {
+                if let Some(snapshot) = self.history.undo(self.snapshot()) {
+                    self.restore(snapshot)
+                }
+            }
+            Message::Redo => {
+                if let Some(snapshot) = self.history.redo(self.snapshot()) {
+                    self.restore(snapshot)
+                }
+            }
             //Message::ModuleBuilder(action) => return self.new_module_content.perform(action).map(Message::ModuleBuilder),
         }
         Task::none()
     }
 
     pub fn view(&self) -> Element<'_, Message> {
-        let underlay = container(
-            storage_tree::column::Column::new(
-                &self.content,
-                Message::ModuleTree,
-                |name, path| folder_to_element(name, path),
-                |_, _| text("Foo").into(),
-            )
-            .space(MENU_OFFSET)
-            .icons_default(PADDING),
+        let mut tree = storage_tree::column::Column::new(
+            &self.content,
+            Message::ModuleTree,
+            |name, path| {
+                let total = self.totals.get(&path).map(StorageTree::value);
+                folder_to_element(name, total.copied().unwrap_or_default(), path)
+            },
+            |entry, path| {
+                let matches = self
+                    .search
+                    .as_ref()
+                    .map(|results| results.get(&path))
+                    .unwrap_or_default();
+                let planned = self.planned.get(&entry.module.id);
+                module_to_element(&entry.module, entry.key, planned, matches, path)
+            },
         )
-        .width(Fill)
-        .height(Fill)
-        .into();
+        .space(MENU_OFFSET)
+        .icons_default(PADDING)
+        .draggable();
+        if let Some(results) = &self.search {
+            tree = tree.filter(|path| results.is_visible(path));
+        }
+
+        let search_bar = row![
+            transparent_text_input(
+                "Search modules, Enter opens the best match...",
+                &self.search_input
+            )
+                .on_input(Message::EditSearch)
+                .on_submit(Message::SubmitSearch),
+        ]
+        .push_maybe(self.search.as_ref().map(|results| {
+            text(format!(
+                "{} module{} found",
+                results.len(),
+                if results.len() == 1 { "" } else { "s" }
+            ))
+        }))
+        .push_maybe(self.search.is_some().then(|| {
+            button(text("Clear"))
+                .style(button::text)
+                .on_press(Message::EditSearch(String::new()))
+        }))
+        .spacing(PADDING)
+        .align_y(iced::alignment::Vertical::Center);
+
+        let underlay = container(column![search_bar, tree].spacing(PADDING))
+            .width(Fill)
+            .height(Fill)
+            .into();
 
         let overlay = match self.overlay {
             Overlay::None => return underlay,
@@ -119,6 +765,69 @@ impl ModuleTree {
                 ]
                 .width(Shrink),
             ),
+            Overlay::Rename => container(
+                column![
+                    text("New name:"),
+                    transparent_text_input("Type here...", &self.rename_input)
+                        .on_input(Message::EditRename)
+                        .on_submit(Message::Rename(self.path.clone())),
+                ]
+                .width(Shrink),
+            ),
+            Overlay::Move => container(
+                column![text("Move to:")]
+                    .extend(
+                        self.folders()
+                            .into_iter()
+                            .filter(|(folder, _, _)| !folder.starts_with(&self.path))
+                            .map(|(folder, _, name)| {
+                                button(text(name))
+                                    .style(button::text)
+                                    .on_press(Message::MoveTo(self.path.clone(), folder))
+                                    .into()
+                            }),
+                    )
+                    .spacing(PADDING / 2)
+                    .width(Shrink),
+            ),
+            Overlay::Plan => container(
+                column![
+                    text("Plan for semester:"),
+                    transparent_text_input("Winter semester 2025/26", &self.plan_input)
+                        .on_input(Message::EditPlan)
+                        .on_submit(Message::Plan(self.path.clone())),
+                ]
+                .push_maybe(self.plan_error.as_ref().map(|err| {
+                    container(text(err)).style(backgrounded(container::transparent, ERROR_COLOR))
+                }))
+                .spacing(PADDING / 2)
+                .width(Shrink),
+            ),
+            Overlay::Delete => {
+                let name = match self.content.get(&self.path) {
+                    Some(StorageTree::Node(node)) => node.get_key().name.clone(),
+                    _ => String::new(),
+                };
+                let total = self.totals.get(&self.path).map(StorageTree::value);
+                let modules = total.copied().unwrap_or_default().modules;
+                container(
+                    column![
+                        text(format!(
+                            "Delete {name} and the {modules} module{} in it?",
+                            if modules == 1 { "" } else { "s" }
+                        )),
+                        row![
+                            button(text("Cancel")).on_press(Message::OverlayQuit),
+                            button(text("Delete"))
+                                .style(button::danger)
+                                .on_press(Message::Delete(self.path.clone())),
+                        ]
+                        .spacing(PADDING),
+                    ]
+                    .spacing(PADDING / 2)
+                    .width(Shrink),
+                )
+            }
             // Overlay::Module => {
             //     let module_display: Element<'_, _> =
             //         ModuleDisplay::new(&self.new_module_content).into();
@@ -166,7 +875,7 @@ impl Window for NewModulePopup {
     fn update(
         &mut self,
         message: Self::Message,
-        _popup_maker: PopupMaker,
+        popup_maker: PopupMaker,
     ) -> impl Into<Task<Self::Message>> {
         match message {
             NewModulePopupMsg::ModuleDisplay(action) => self
@@ -174,8 +883,8 @@ impl Window for NewModulePopup {
                 .perform(action)
                 .map(NewModulePopupMsg::ModuleDisplay),
             NewModulePopupMsg::Done => {
-                let _ = self.tx.send(self.content.replace_module(Module::default()));
-                Task::none()
+                let _ = self.tx.try_send(self.content.replace_module(Module::default()));
+                popup_maker.close()
             }
         }
     }
@@ -199,12 +908,244 @@ impl Window for NewModulePopup {
     }
 }
 
-pub fn folder_to_element<'a, M>(
-    node: &'a Node<MetaKey<String, NodeState>, M>,
+/// Window displaying a module of the tree.
+///
+/// Every edit is sent back to the tree, and the module is shown again when something else changes
+/// it. Undoing in the window undoes the last edit of the whole tree, as in the main window.
+#[derive(Debug)]
+struct ModuleWindow {
+    content: module_display::Content,
+    editing: bool,
+    tx: mpsc::UnboundedSender<ModuleWindowEvent>,
+}
+
+/// What a [ModuleWindow] sends to the tree.
+#[derive(Debug, Clone)]
+enum ModuleWindowEvent {
+    Edit(Box<Module>),
+    Undo,
+    Redo,
+}
+
+#[derive(Debug, Clone)]
+enum ModuleWindowMsg {
+    ModuleDisplay(module_display::Action),
+    ToggleEdit,
+    Undo,
+    Redo,
+    /// The module changed in the tree, or was deleted if there is none.
+    Refresh(Option<Box<Module>>),
+}
+
+impl ModuleWindow {
+    fn new(
+        module: Module,
+        tx: mpsc::UnboundedSender<ModuleWindowEvent>,
+        refreshes: mpsc::UnboundedReceiver<Option<Box<Module>>>,
+    ) -> (Self, Task<ModuleWindowMsg>) {
+        let mut content = module_display::Content::new(module);
+        content.expand_all(true);
+        // The refreshes end when the tree is replaced, by a new or opened project.
+        let refreshes = refreshes.chain(stream::once(async { None }));
+        (
+            Self {
+                content,
+                editing: false,
+                tx,
+            },
+            Task::run(refreshes, ModuleWindowMsg::Refresh),
+        )
+    }
+}
+
+impl Window for ModuleWindow {
+    type Message = ModuleWindowMsg;
+
+    fn update(
+        &mut self,
+        message: Self::Message,
+        popup_maker: PopupMaker,
+    ) -> impl Into<Task<Self::Message>> {
+        match message {
+            ModuleWindowMsg::ModuleDisplay(action) => {
+                let before = self.content.module.clone();
+                let task = self
+                    .content
+                    .perform(action)
+                    .map(ModuleWindowMsg::ModuleDisplay);
+                if self.content.module != before {
+                    let module = Box::new(self.content.module.clone());
+                    let _ = self.tx.unbounded_send(ModuleWindowEvent::Edit(module));
+                }
+                task
+            }
+            ModuleWindowMsg::Undo => {
+                let _ = self.tx.unbounded_send(ModuleWindowEvent::Undo);
+                Task::none()
+            }
+            ModuleWindowMsg::Redo => {
+                let _ = self.tx.unbounded_send(ModuleWindowEvent::Redo);
+                Task::none()
+            }
+            ModuleWindowMsg::Refresh(Some(module)) => {
+                if *module != self.content.module {
+                    self.content.replace_module(*module);
+                }
+                Task::none()
+            }
+            ModuleWindowMsg::Refresh(None) => popup_maker.close(),
+            ModuleWindowMsg::ToggleEdit => {
+                self.editing = !self.editing;
+                self.content.set_all_edits(self.editing);
+                Task::none()
+            }
+        }
+    }
+
+    fn view(&self) -> impl Into<Element<'_, Self::Message>> {
+        let edit_button = if self.editing {
+            button(bald_text("Done").width(Fill).center()).style(button::success)
+        } else {
+            button(bald_text("Edit").width(Fill).center())
+        }
+        .on_press(ModuleWindowMsg::ToggleEdit);
+
+        center(
+            column![
+                scrollable(
+                    <_ as Into<Element<'_, _>>>::into(ModuleDisplay::new(&self.content))
+                        .map(ModuleWindowMsg::ModuleDisplay)
+                )
+                .height(Fill)
+                .spacing(PADDING),
+                horizontal_rule(PADDING),
+                edit_button
+            ]
+            .height(Fill)
+            .width(Fill),
+        )
+        .padding(PADDING)
+    }
+
+    fn title(&self) -> String {
+        self.content.name.clone()
+    }
+
+    fn on_key_press(
+        &self,
+        key: iced::keyboard::Key,
+        modifiers: iced::keyboard::Modifiers,
+    ) -> Option<Self::Message> {
+        history_shortcut(&key, modifiers).map(|step| match step {
+            HistoryStep::Undo => ModuleWindowMsg::Undo,
+            HistoryStep::Redo => ModuleWindowMsg::Redo,
+        })
+    }
+}
+
+/// Displays a module of the tree, with where the search matched it. Clicking on it opens it in a new window.
+///
+/// Its context menu plans it for a semester, and renames, copies, moves or deletes it.
+pub fn module_to_element<'a>(
+    module: &'a Module,
+    key: ModuleKey,
+    planned: Option<&Semester>,
+    matches: &'a [search::Match],
+    path: Path,
+) -> Element<'a, Message> {
+    let label = button(
+        row![
+            text(module.get_name()),
+            text(format!("({})", module.get_id())),
+            text(format!("{} ECTS", module.get_ects())),
+        ]
+        .push_maybe(planned.map(|semester| text(format!("- planned in {semester}"))))
+        .spacing(PADDING),
+    )
+    .on_press(Message::OpenModule(key))
+    .padding(0)
+    .style(button::text);
+
+    let is_planned = planned.is_some();
+    let menu = ContextMenu::new(label, move || {
+        container(
+            column![
+                button("Plan for semester...")
+                    .style(button::text)
+                    .on_press(Message::PlanPressed(path.clone())),
+            ]
+            .push_maybe(is_planned.then(|| {
+                button("Remove from plan")
+                    .style(button::text)
+                    .on_press(Message::Unplan(path.clone()))
+            }))
+            .push(
+                button("Update from page...")
+                    .style(button::text)
+                    .on_press(Message::UpdatePressed(path.clone())),
+            )
+            .push(structure_menu(&path)),
+        )
+        .style(container::rounded_box)
+        .into()
+    });
+
+    column![menu]
+        .extend(matches.iter().map(match_to_element))
+        .into()
+}
+
+/// Number of characters shown on each side of a search match.
+const MATCH_CONTEXT: usize = 40;
+
+/// Shows the field in which the search matched, and the line of its text around the match, highlighted.
+fn match_to_element<'a>(found: &'a search::Match) -> Element<'a, Message> {
+    let search::Match { field, text, range } = found;
+    let line_start = text[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[range.end..]
+        .find('\n')
+        .map_or(text.len(), |i| range.end + i);
+
+    let before = &text[line_start..range.start];
+    let skipped = before.chars().count().saturating_sub(MATCH_CONTEXT);
+    let before = &before[before.char_indices().nth(skipped).map_or(0, |(i, _)| i)..];
+    let after = &text[range.end..line_end];
+    let cut = after.char_indices().nth(MATCH_CONTEXT).map(|(i, _)| i);
+    let after = &after[..cut.unwrap_or(after.len())];
+
+    rich_text([
+        span(format!("{field}: ")).font(iced::Font {
+            weight: iced::font::Weight::Bold,
+            ..iced::Font::DEFAULT
+        }),
+        span(if skipped > 0 { "…" } else { "" }),
+        span(before),
+        span(&text[range.clone()]).background(HIGHLIGHT_COLOR),
+        span(after),
+        span(if cut.is_some() { "…" } else { "" }),
+    ])
+    .size(12)
+    .into()
+}
+
+fn folder_to_element<'a, M>(
+    node: &'a Node<MetaKey<Folder, NodeState>, M>,
+    total: FolderTotal,
     path: Path,
 ) -> Element<'a, Message> {
-    let name = &**node.get_key();
-    let label = text(name).center();
+    let name = &node.get_key().name;
+    let label = row![
+        text(name).center(),
+        text(format!(
+            "{} ECTS, {} module{}",
+            total.ects,
+            total.modules,
+            if total.modules == 1 { "" } else { "s" }
+        ))
+        .style(text::secondary),
+    ]
+    .spacing(PADDING)
+    .align_y(iced::alignment::Vertical::Center);
 
     ContextMenu::new(label, move || {
         container(column![
@@ -214,6 +1155,7 @@ pub fn folder_to_element<'a, M>(
             button("New module")
                 .style(button::text)
                 .on_press(Message::NewModulePressed(path.clone())),
+            structure_menu(&path),
         ])
         .style(container::rounded_box)
         .into()
@@ -221,20 +1163,23 @@ pub fn folder_to_element<'a, M>(
     .into()
 }
 
-fn modal<'a, Message>(
-    base: impl Into<Element<'a, Message>>,
-    content: impl Into<Element<'a, Message>>,
-    on_blur: Message,
-) -> Element<'a, Message>
-where
-    Message: Clone + 'a,
-{
-    stack![
-        base.into(),
-        opaque(
-            mouse_area(center(opaque(content)).style(|_| background(INACTIVE_COLOR)))
-                .on_press(on_blur)
-        )
+/// Entries of the context menus that rename, copy, move or delete the sub tree at the given path.
+///
+/// Only renaming is possible for the root folder.
+fn structure_menu<'a>(path: &Path) -> Element<'a, Message> {
+    let entry = |label, message| button(label).style(button::text).on_press(message);
+    let rename = entry("Rename...", Message::RenamePressed(path.clone()));
+    if path.is_empty() {
+        return rename.into();
+    }
+    column![
+        rename,
+        entry("Duplicate", Message::Duplicate(path.clone())),
+        entry("Move to...", Message::MovePressed(path.clone())),
+        entry("Move up", Message::MoveUp(path.clone())),
+        entry("Move down", Message::MoveDown(path.clone())),
+        entry("Delete", Message::DeletePressed(path.clone())).style(button::danger),
     ]
     .into()
 }
+
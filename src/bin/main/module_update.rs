@@ -0,0 +1,274 @@
+//! Window updating a module from a page copied from TUMonline, showing what changes first.
+
+use std::{fmt::Display, io::Cursor};
+
+use iced::{
+    Element,
+    Length::Fill,
+    Task,
+    alignment::Vertical,
+    futures::channel::mpsc,
+    widget::{
+        Column, button, checkbox, column, container, horizontal_space, pick_list, row, scrollable,
+        text, text_editor,
+    },
+};
+use tum_module_picker::{
+    module::{Change, FieldValue, Module, course::Course, exam::Exam},
+    window_stack::{PopupMaker, Window},
+};
+
+use super::*;
+
+/// The kind of TUMonline page pasted in the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageKind {
+    /// The page of the module: its fields found in the page replace the current ones.
+    Module,
+    /// The page of a course: it replaces the course with the same number, or is added.
+    Course,
+    /// The page of an exam: it replaces the exam with the same number, or is added.
+    Exam,
+}
+
+impl PageKind {
+    const ALL: [PageKind; 3] = [PageKind::Module, PageKind::Course, PageKind::Exam];
+}
+
+impl Display for PageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PageKind::Module => write!(f, "Module page"),
+            PageKind::Course => write!(f, "Course page"),
+            PageKind::Exam => write!(f, "Exam page"),
+        }
+    }
+}
+
+/// Window updating a module from a pasted page.
+///
+/// The changes are listed field by field before anything is overwritten, and only the chosen ones
+/// are sent back to the tree, which merges them into its current version of the module.
+#[derive(Debug)]
+pub struct ModuleUpdate {
+    module: Module,
+    kind: PageKind,
+    page: text_editor::Content,
+    /// The changes found in the page, and whether each of them is chosen, once compared.
+    changes: Option<Vec<(Change, bool)>>,
+    tx: mpsc::UnboundedSender<Vec<Change>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ModuleUpdateMsg {
+    SelectKind(PageKind),
+    EditPage(text_editor::Action),
+    Compare,
+    Toggle(usize, bool),
+    ToggleAll(bool),
+    Back,
+    Apply,
+}
+
+/// Replaces the item with the same number, or title if it has none, or adds it.
+fn replace_or_push<T>(items: &mut Vec<T>, item: T, key: impl Fn(&T) -> (&String, &String)) {
+    let matches = |other: &T| {
+        let ((number, title), (other_number, other_title)) = (key(&item), key(other));
+        if number.is_empty() {
+            title == other_title
+        } else {
+            number == other_number
+        }
+    };
+    match items.iter().position(matches) {
+        Some(index) => items[index] = item,
+        None => items.push(item),
+    }
+}
+
+impl ModuleUpdate {
+    /// Creates the window updating the given module.
+    pub fn new(
+        module: Module,
+        tx: mpsc::UnboundedSender<Vec<Change>>,
+    ) -> (Self, Task<ModuleUpdateMsg>) {
+        (
+            Self {
+                module,
+                kind: PageKind::Module,
+                page: text_editor::Content::new(),
+                changes: None,
+                tx,
+            },
+            Task::none(),
+        )
+    }
+
+    /// The module as it would be with everything read from the page.
+    fn updated(&self) -> Module {
+        let page = Cursor::new(self.page.text());
+        let mut updated = self.module.clone();
+        match self.kind {
+            PageKind::Module => {
+                let (module, report) = Module::from_reader(page);
+                for field in report.found {
+                    let _ = updated.set(field, module.get(field));
+                }
+            }
+            PageKind::Course => {
+                replace_or_push(&mut updated.courses, Course::from_reader(page), |course| {
+                    (course.get_number(), course.get_title())
+                })
+            }
+            PageKind::Exam => {
+                replace_or_push(&mut updated.exams, Exam::from_reader(page), |exam| {
+                    (exam.get_number(), exam.get_title())
+                })
+            }
+        }
+        updated
+    }
+
+    /// Shows a change, with the old and new text of long fields.
+    fn change_to_element(
+        index: usize,
+        change: &Change,
+        chosen: bool,
+    ) -> Element<'_, ModuleUpdateMsg> {
+        let toggle = checkbox(change.to_string(), chosen)
+            .on_toggle(move |chosen| ModuleUpdateMsg::Toggle(index, chosen));
+        let texts = match change {
+            Change::Value { old, new, .. } => Some((old, new)),
+            Change::Course(_) | Change::Exam(_) => None,
+        }
+        .filter(|(old, new)| {
+            let long = |value: &FieldValue| value.to_string().contains('\n');
+            long(old) || long(new)
+        });
+
+        match texts {
+            None => toggle.into(),
+            Some((old, new)) => column![
+                toggle,
+                row![
+                    container(text(old.to_string()))
+                        .style(backgrounded(container::bordered_box, ERROR_COLOR))
+                        .padding(PADDING / 2)
+                        .width(Fill),
+                    container(text(new.to_string()))
+                        .style(container::bordered_box)
+                        .padding(PADDING / 2)
+                        .width(Fill),
+                ]
+                .spacing(PADDING),
+            ]
+            .spacing(PADDING / 2)
+            .into(),
+        }
+    }
+}
+
+impl Window for ModuleUpdate {
+    type Message = ModuleUpdateMsg;
+
+    fn update(
+        &mut self,
+        message: Self::Message,
+        popup_maker: PopupMaker,
+    ) -> impl Into<Task<Self::Message>> {
+        match message {
+            ModuleUpdateMsg::SelectKind(kind) => self.kind = kind,
+            ModuleUpdateMsg::EditPage(action) => self.page.perform(action),
+            ModuleUpdateMsg::Compare => {
+                let changes = self.module.diff(&self.updated());
+                self.changes = Some(changes.into_iter().map(|change| (change, true)).collect());
+            }
+            ModuleUpdateMsg::Toggle(index, chosen) => {
+                if let Some((_, value)) = self
+                    .changes
+                    .as_mut()
+                    .and_then(|changes| changes.get_mut(index))
+                {
+                    *value = chosen
+                }
+            }
+            ModuleUpdateMsg::ToggleAll(chosen) => {
+                for (_, value) in self.changes.iter_mut().flatten() {
+                    *value = chosen
+                }
+            }
+            ModuleUpdateMsg::Back => self.changes = None,
+            ModuleUpdateMsg::Apply => {
+                if let Some(changes) = &self.changes {
+                    let chosen = changes
+                        .iter()
+                        .filter(|(_, chosen)| *chosen)
+                        .map(|(change, _)| change.clone())
+                        .collect();
+                    let _ = self.tx.unbounded_send(chosen);
+                }
+                return popup_maker.close();
+            }
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> impl Into<Element<'_, Self::Message>> {
+        let content: Element<'_, _> = match &self.changes {
+            None => column![
+                row![
+                    text("Paste a page copied from TUMonline:"),
+                    horizontal_space(),
+                    pick_list(PageKind::ALL, Some(self.kind), ModuleUpdateMsg::SelectKind),
+                ]
+                .align_y(Vertical::Center),
+                text_editor(&self.page)
+                    .placeholder(self.kind.to_string())
+                    .on_action(ModuleUpdateMsg::EditPage)
+                    .height(Fill),
+                button(bald_text("Compare").width(Fill).center())
+                    .on_press(ModuleUpdateMsg::Compare),
+            ]
+            .spacing(PADDING)
+            .into(),
+            Some(changes) => {
+                let list: Element<'_, _> = if changes.is_empty() {
+                    text("The page does not change anything.").into()
+                } else {
+                    scrollable(
+                        Column::with_children(changes.iter().enumerate().map(
+                            |(index, (change, chosen))| {
+                                Self::change_to_element(index, change, *chosen)
+                            },
+                        ))
+                        .spacing(PADDING),
+                    )
+                    .height(Fill)
+                    .into()
+                };
+                let chosen = changes.iter().filter(|(_, chosen)| *chosen).count();
+                column![
+                    list,
+                    row![
+                        button(text("Back")).on_press(ModuleUpdateMsg::Back),
+                        button(text("Select all")).on_press(ModuleUpdateMsg::ToggleAll(true)),
+                        button(text("Select none")).on_press(ModuleUpdateMsg::ToggleAll(false)),
+                        horizontal_space(),
+                        button(bald_text(format!("Apply {chosen} changes")))
+                            .style(button::success)
+                            .on_press_maybe((chosen > 0).then_some(ModuleUpdateMsg::Apply)),
+                    ]
+                    .spacing(PADDING)
+                    .align_y(Vertical::Center),
+                ]
+                .spacing(PADDING)
+                .into()
+            }
+        };
+        container(content).padding(PADDING)
+    }
+
+    fn title(&self) -> String {
+        format!("Update {}", self.module.name)
+    }
+}
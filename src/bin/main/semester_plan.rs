@@ -0,0 +1,200 @@
+//! Window showing the study plan, with a column per semester.
+
+use iced::{
+    Element,
+    Length::{Fill, Shrink},
+    Task,
+    alignment::Vertical,
+    futures::channel::mpsc,
+    widget::{Column, button, column, container, horizontal_space, row, scrollable, text},
+};
+use tum_module_picker::{
+    academic_calendar::AcademicCalendar,
+    module::{ECTS, Module, semester::Semester},
+    study_plan::StudyPlan,
+    window_stack::{PopupMaker, Window},
+};
+
+use super::*;
+use crate::module_tree::ModuleKey;
+
+const COLUMN_WIDTH: f32 = 250.;
+
+/// Window showing the planned modules in a column per semester, with their ECTS credits
+/// and the reasons why they should not be taken in that semester.
+/// Each column also shows the lecture and exam periods of its semester.
+///
+/// Modules can be moved to the previous or next semester.
+#[derive(Debug)]
+pub struct SemesterPlan {
+    modules: Vec<(ModuleKey, Module)>,
+    plan: StudyPlan,
+    calendar: AcademicCalendar,
+    tx: mpsc::UnboundedSender<SemesterPlanEvent>,
+}
+
+/// What the study plan window sends to the main window.
+#[derive(Debug, Clone)]
+pub enum SemesterPlanEvent {
+    /// A module was clicked: open it.
+    Open(ModuleKey),
+    /// The module with the given id was moved to a semester.
+    Assign(String, Semester),
+}
+
+#[derive(Debug, Clone)]
+pub enum SemesterPlanMsg {
+    Open(ModuleKey),
+    Move(String, Semester),
+}
+
+impl SemesterPlan {
+    /// Creates the window from the planned modules, given with their key, the plan
+    /// and the academic calendar.
+    pub fn new(
+        modules: Vec<(ModuleKey, Module)>,
+        plan: StudyPlan,
+        calendar: AcademicCalendar,
+        tx: mpsc::UnboundedSender<SemesterPlanEvent>,
+    ) -> (Self, Task<SemesterPlanMsg>) {
+        (
+            Self {
+                modules,
+                plan,
+                calendar,
+                tx,
+            },
+            Task::none(),
+        )
+    }
+
+    /// The semesters shown: every semester from the first planned one to the one after the last,
+    /// so that modules can be moved to a semester that is still empty.
+    fn semesters(&self) -> Vec<Semester> {
+        let planned = self.plan.semesters();
+        let (Some(first), Some(last)) = (planned.first(), planned.last()) else {
+            return Vec::new();
+        };
+        first.until(&last.next()).collect()
+    }
+
+    /// The column of a semester.
+    fn semester_column(&self, semester: &Semester, ects: ECTS) -> Element<'_, SemesterPlanMsg> {
+        let modules = self
+            .modules
+            .iter()
+            .filter(|(_, module)| self.plan.get(&module.id) == Some(semester))
+            .map(|(key, module)| {
+                let issues = StudyPlan::check(module, semester);
+                let moves = row![
+                    button(text("<")).on_press_maybe(
+                        semester
+                            .prev()
+                            .map(|prev| SemesterPlanMsg::Move(module.id.clone(), prev))
+                    ),
+                    horizontal_space(),
+                    button(text(">"))
+                        .on_press(SemesterPlanMsg::Move(module.id.clone(), semester.next())),
+                ];
+                container(
+                    column![
+                        button(bald_text(&module.name))
+                            .style(button::text)
+                            .on_press(SemesterPlanMsg::Open(*key)),
+                        text(format!("{} - {} ECTS", module.id, module.ects)),
+                    ]
+                    .extend(issues.iter().map(|issue| {
+                        container(text(issue.to_string()))
+                            .style(backgrounded(container::transparent, ERROR_COLOR))
+                            .width(Fill)
+                            .into()
+                    }))
+                    .push(moves)
+                    .spacing(PADDING / 2),
+                )
+                .style(container::bordered_box)
+                .padding(PADDING / 2)
+                .width(Fill)
+                .into()
+            });
+
+        let (lecture_start, lecture_end) = self.calendar.lecture_period(semester);
+        let exam_periods = self.calendar.exam_periods(semester).iter().map(|period| {
+            text(format!(
+                "{}: {} - {}",
+                period.name, period.start, period.end
+            ))
+            .into()
+        });
+
+        column![
+            bald_text(semester.to_string()),
+            text(format!("Lectures: {lecture_start} - {lecture_end}")),
+        ]
+        .extend(exam_periods)
+        .push(text(format!("{ects} ECTS")))
+        .extend(modules)
+        .spacing(PADDING)
+        .width(COLUMN_WIDTH)
+        .into()
+    }
+}
+
+impl Window for SemesterPlan {
+    type Message = SemesterPlanMsg;
+
+    fn update(
+        &mut self,
+        message: Self::Message,
+        _popup_maker: PopupMaker,
+    ) -> impl Into<Task<Self::Message>> {
+        match message {
+            SemesterPlanMsg::Open(key) => {
+                let _ = self.tx.unbounded_send(SemesterPlanEvent::Open(key));
+            }
+            SemesterPlanMsg::Move(id, semester) => {
+                self.plan.assign(id.clone(), semester.clone());
+                let _ = self
+                    .tx
+                    .unbounded_send(SemesterPlanEvent::Assign(id, semester));
+            }
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> impl Into<Element<'_, Self::Message>> {
+        let totals = self
+            .plan
+            .ects(self.modules.iter().map(|(_, module)| module));
+        let semesters = self.semesters();
+        if semesters.is_empty() {
+            return container(text("No planned module")).padding(PADDING);
+        }
+
+        let columns = row(semesters.iter().map(|semester| {
+            let ects = totals
+                .iter()
+                .find(|(planned, _)| planned == semester)
+                .map(|(_, ects)| *ects)
+                .unwrap_or_default();
+            self.semester_column(semester, ects)
+        }))
+        .spacing(PADDING)
+        .align_y(Vertical::Top);
+
+        container(
+            scrollable(Column::new().push(columns).width(Shrink))
+                .direction(scrollable::Direction::Both {
+                    vertical: scrollable::Scrollbar::default(),
+                    horizontal: scrollable::Scrollbar::default(),
+                })
+                .width(Fill)
+                .height(Fill),
+        )
+        .padding(PADDING)
+    }
+
+    fn title(&self) -> String {
+        "Study plan".to_string()
+    }
+}
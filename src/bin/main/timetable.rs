@@ -0,0 +1,736 @@
+//! Window showing the appointments of the planned modules on a week grid.
+
+use iced::{
+    Color, Element,
+    Length::Fill,
+    Point, Rectangle, Renderer, Size, Task, Theme,
+    alignment::{Horizontal, Vertical},
+    futures::channel::{mpsc, oneshot},
+    mouse,
+    widget::{
+        Column, button, canvas,
+        canvas::{Frame, Geometry, Path as CanvasPath, Stroke, event},
+        column, horizontal_space, pick_list, row, text,
+    },
+};
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Weekday};
+use tum_module_picker::{
+    academic_calendar::AcademicCalendar,
+    conflict::find_conflicts,
+    module::{Module, appointment::Appointment, semester::Semester},
+    schedule::{GroupSelection, Preference, Solution, course_key, solve},
+    window_stack::{PopupMaker, Window},
+};
+
+use super::*;
+use crate::module_tree::ModuleKey;
+
+/// Colors given to the modules, in turn.
+const MODULE_COLORS: [Color; 8] = [
+    Color::from_rgb(0.31, 0.47, 0.65),
+    Color::from_rgb(0.95, 0.56, 0.17),
+    Color::from_rgb(0.35, 0.63, 0.31),
+    Color::from_rgb(0.69, 0.48, 0.63),
+    Color::from_rgb(0.46, 0.72, 0.7),
+    Color::from_rgb(0.93, 0.79, 0.28),
+    Color::from_rgb(0.61, 0.46, 0.37),
+    Color::from_rgb(1., 0.62, 0.65),
+];
+const CONFLICT_COLOR: Color = Color::from_rgb(0.9, 0., 0.);
+
+const HOUR_LABEL_WIDTH: f32 = 45.;
+const DAY_LABEL_HEIGHT: f32 = 25.;
+const TEXT_SIZE: f32 = 12.;
+/// Number of solutions of the group solver shown.
+const SHOWN_SOLUTIONS: usize = 5;
+
+/// Window showing the appointments of the modules planned for a semester, one week at a time.
+///
+/// Only the chosen groups of the courses are shown.
+/// The groups can be chosen among the combinations without conflicts found by [solve].
+/// Holidays and exam periods come from the [AcademicCalendar].
+#[derive(Debug)]
+pub struct Timetable {
+    modules: Vec<(ModuleKey, Module, Semester)>,
+    semesters: Vec<Semester>,
+    semester: Option<Semester>,
+    /// First day of the displayed week.
+    monday: Date,
+    groups: GroupSelection,
+    calendar: AcademicCalendar,
+    preference: Preference,
+    /// Combinations found for the displayed semester, if the solver ran.
+    solutions: Option<Vec<Solution>>,
+    /// Indicates whether the solver is running.
+    solving: bool,
+    tx: mpsc::UnboundedSender<TimetableEvent>,
+}
+
+/// What the timetable sends to the main window.
+#[derive(Debug, Clone)]
+pub enum TimetableEvent {
+    /// An appointment was clicked: open its module.
+    Open(ModuleKey),
+    /// Groups were chosen.
+    SelectGroups(GroupSelection),
+}
+
+#[derive(Debug, Clone)]
+pub enum TimetableMsg {
+    SelectSemester(Semester),
+    PreviousWeek,
+    NextWeek,
+    Open(ModuleKey),
+    SelectPreference(Preference),
+    Solve,
+    Solved(Semester, Preference, Vec<Solution>),
+    Apply(usize),
+}
+
+/// An appointment placed on the grid.
+#[derive(Debug, Clone)]
+struct Block {
+    module: ModuleKey,
+    color: Color,
+    title: String,
+    place: String,
+    start: PrimitiveDateTime,
+    stop: PrimitiveDateTime,
+    conflict: bool,
+    /// Indicates whether the appointment falls on a holiday, so probably does not take place.
+    holiday: bool,
+    /// Position of the block among the blocks of its day that overlap.
+    lane: usize,
+}
+
+/// The course group or exam of a module that an appointment belongs to, by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Item<'a> {
+    Course(usize, &'a String),
+    Exam(usize),
+}
+
+/// Owner of a block when looking for conflicts: the appointments of the same course group
+/// or exam of a module never conflict with each other.
+#[derive(Debug, Clone)]
+struct BlockOwner<'a> {
+    block: usize,
+    module: usize,
+    item: Item<'a>,
+}
+
+impl PartialEq for BlockOwner<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.module == other.module && self.item == other.item
+    }
+}
+
+/// Monday of the week of the given date.
+fn monday_of(date: Date) -> Date {
+    date - Duration::days(date.weekday().number_days_from_monday() as i64)
+}
+
+impl Timetable {
+    /// Creates the window from the planned modules, given with their key and semester,
+    /// the chosen groups and the academic calendar.
+    pub fn new(
+        modules: Vec<(ModuleKey, Module, Semester)>,
+        groups: GroupSelection,
+        calendar: AcademicCalendar,
+        tx: mpsc::UnboundedSender<TimetableEvent>,
+    ) -> (Self, Task<TimetableMsg>) {
+        let mut semesters: Vec<Semester> = Vec::new();
+        for (_, _, semester) in &modules {
+            if !semesters.contains(semester) {
+                semesters.push(semester.clone())
+            }
+        }
+        semesters.sort();
+
+        let current = Semester::current();
+        let semester = semesters
+            .iter()
+            .find(|semester| **semester == current)
+            .or(semesters.first())
+            .cloned();
+
+        let mut timetable = Self {
+            modules,
+            semesters,
+            semester: None,
+            monday: monday_of(OffsetDateTime::now_utc().date()),
+            groups,
+            calendar,
+            preference: Preference::default(),
+            solutions: None,
+            solving: false,
+            tx,
+        };
+        if let Some(semester) = semester {
+            timetable.select(semester);
+        }
+        (timetable, Task::none())
+    }
+
+    /// Shows the given semester, from the week of its first appointment during the semester,
+    /// or else from the start of its lecture period.
+    fn select(&mut self, semester: Semester) {
+        let first = self
+            .appointments(&semester)
+            .filter(|(_, _, _, _, appointment)| appointment.semester() == semester)
+            .map(|(_, _, _, _, appointment)| appointment.get_start().date())
+            .min();
+        let (lecture_start, _) = self.calendar.lecture_period(&semester);
+        self.monday = monday_of(first.unwrap_or(lecture_start));
+        self.semester = Some(semester);
+        self.solutions = None;
+        self.solving = false;
+    }
+
+    /// Searches the best combinations of groups without conflicts for the displayed semester,
+    /// on another thread, as the search can take a while.
+    fn solve(&mut self) -> Task<TimetableMsg> {
+        let Some(semester) = self.semester.clone() else {
+            return Task::none();
+        };
+        let modules: Vec<Module> = self
+            .modules
+            .iter()
+            .filter(|(_, _, planned)| *planned == semester)
+            .map(|(_, module, _)| module.clone())
+            .collect();
+        let preference = self.preference;
+        self.solving = true;
+        let (tx, rx) = oneshot::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(solve(&modules, preference, SHOWN_SOLUTIONS));
+        });
+        Task::perform(rx, move |solutions| {
+            TimetableMsg::Solved(semester.clone(), preference, solutions.unwrap_or_default())
+        })
+    }
+
+    /// Short description of the groups chosen by a solution.
+    fn describe(&self, solution: &Solution) -> String {
+        let names: Vec<String> = solution
+            .groups
+            .iter()
+            .map(|(id, key, group)| {
+                let course = self
+                    .modules
+                    .iter()
+                    .filter(|(_, module, _)| module.id == *id)
+                    .flat_map(|(_, module, _)| &module.courses)
+                    .find(|course| course_key(course) == key);
+                match course {
+                    Some(course) if !course.get_title().is_empty() => {
+                        format!("{}: {group}", course.get_title())
+                    }
+                    _ => format!("{key}: {group}"),
+                }
+            })
+            .collect();
+        names.join(", ")
+    }
+
+    /// Every appointment of the chosen groups and exams of the modules planned in the semester,
+    /// with the index and key of its module, the item it belongs to and a title.
+    fn appointments<'a>(
+        &'a self,
+        semester: &'a Semester,
+    ) -> impl Iterator<Item = (usize, ModuleKey, Item<'a>, String, &'a Appointment)> + 'a {
+        self.modules
+            .iter()
+            .enumerate()
+            .filter(move |(_, (_, _, planned))| planned == semester)
+            .flat_map(move |(index, (key, module, _))| {
+                let courses = module
+                    .courses
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(item, course)| {
+                        course
+                            .get_dates()
+                            .iter()
+                            .filter(move |(group, _)| self.groups.is_chosen(module, course, group))
+                            .flat_map(move |(group, dates)| {
+                                let title = if course.get_title().is_empty() {
+                                    module.name.clone()
+                                } else if course.get_dates().len() > 1 {
+                                    format!("{} ({group})", course.get_title())
+                                } else {
+                                    course.get_title().clone()
+                                };
+                                dates.iter().map(move |appointment| {
+                                    let item = Item::Course(item, group);
+                                    (index, *key, item, title.clone(), appointment)
+                                })
+                            })
+                    });
+                let exams = module
+                    .exams
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(item, exam)| {
+                        exam.get_dates().iter().map(move |appointment| {
+                            let title = format!("Exam: {}", module.name);
+                            (index, *key, Item::Exam(item), title, appointment)
+                        })
+                    });
+                courses.chain(exams)
+            })
+    }
+
+    /// The blocks of the displayed week.
+    fn blocks(&self) -> Vec<Block> {
+        let Some(semester) = &self.semester else {
+            return Vec::new();
+        };
+        let week = self.monday..self.monday + Duration::weeks(1);
+
+        let mut appointments: Vec<_> = self
+            .appointments(semester)
+            .filter(|(_, _, _, _, appointment)| week.contains(&appointment.get_start().date()))
+            .collect();
+        appointments.sort_by_key(|(_, _, _, _, appointment)| {
+            (*appointment.get_start(), *appointment.get_stop())
+        });
+
+        let conflicts = find_conflicts(appointments.iter().enumerate().map(
+            |(block, (module, _, item, _, appointment))| {
+                (
+                    BlockOwner {
+                        block,
+                        module: *module,
+                        item: *item,
+                    },
+                    *appointment,
+                )
+            },
+        ));
+
+        let mut blocks: Vec<Block> = appointments
+            .iter()
+            .map(|(index, key, _, title, appointment)| Block {
+                module: *key,
+                color: MODULE_COLORS[index % MODULE_COLORS.len()],
+                title: title.clone(),
+                place: appointment.get_place().clone(),
+                start: *appointment.get_start(),
+                stop: *appointment.get_stop(),
+                conflict: false,
+                holiday: self.calendar.on_holiday(appointment).is_some(),
+                lane: 0,
+            })
+            .collect();
+        for conflict in conflicts {
+            blocks[conflict.first.0.block].conflict = true;
+            blocks[conflict.second.0.block].conflict = true;
+        }
+
+        for i in 0..blocks.len() {
+            let used: Vec<usize> = blocks[..i]
+                .iter()
+                .filter(|other| other.start < blocks[i].stop && blocks[i].start < other.stop)
+                .map(|other| other.lane)
+                .collect();
+            blocks[i].lane = (0..).find(|lane| !used.contains(lane)).unwrap_or_default();
+        }
+
+        blocks
+    }
+
+    /// What the displayed week is, if it is not a regular week of lectures:
+    /// the exam period it overlaps, or the lack of lectures.
+    fn week_note(&self) -> Option<String> {
+        let days = (0..7).map(|day| self.monday + Duration::days(day));
+        if let Some(period) = days
+            .clone()
+            .find_map(|date| self.calendar.exam_period(date))
+        {
+            return Some(format!("Exam period: {}", period.name));
+        }
+        let (start, end) = self.calendar.lecture_period(self.semester.as_ref()?);
+        let lectures = days.clone().any(|date| start <= date && date <= end);
+        let holidays = days
+            .take(5)
+            .all(|date| self.calendar.holiday(date).is_some());
+        (!lectures || holidays).then(|| "No lectures".to_string())
+    }
+}
+
+impl Window for Timetable {
+    type Message = TimetableMsg;
+
+    fn update(
+        &mut self,
+        message: Self::Message,
+        _popup_maker: PopupMaker,
+    ) -> impl Into<Task<Self::Message>> {
+        match message {
+            TimetableMsg::SelectSemester(semester) => self.select(semester),
+            TimetableMsg::PreviousWeek => self.monday -= Duration::weeks(1),
+            TimetableMsg::NextWeek => self.monday += Duration::weeks(1),
+            TimetableMsg::Open(key) => {
+                let _ = self.tx.unbounded_send(TimetableEvent::Open(key));
+            }
+            TimetableMsg::SelectPreference(preference) => {
+                self.preference = preference;
+                if self.solutions.is_some() || self.solving {
+                    return self.solve();
+                }
+            }
+            TimetableMsg::Solve => return self.solve(),
+            TimetableMsg::Solved(semester, preference, solutions) => {
+                // Results for a semester or preference that is not shown anymore are outdated.
+                if self.semester.as_ref() == Some(&semester) && self.preference == preference {
+                    self.solutions = Some(solutions);
+                    self.solving = false;
+                }
+            }
+            TimetableMsg::Apply(index) => {
+                let solution = self
+                    .solutions
+                    .as_ref()
+                    .and_then(|solutions| solutions.get(index));
+                if let Some(solution) = solution {
+                    let groups = solution.groups.clone();
+                    self.groups.extend(groups.clone());
+                    let _ = self.tx.unbounded_send(TimetableEvent::SelectGroups(groups));
+                }
+            }
+        }
+        Task::none()
+    }
+
+    fn view(&self) -> impl Into<Element<'_, Self::Message>> {
+        let sunday = self.monday + Duration::days(6);
+        let header = row![
+            pick_list(
+                self.semesters.as_slice(),
+                self.semester.clone(),
+                TimetableMsg::SelectSemester
+            )
+            .placeholder("No planned module"),
+            horizontal_space(),
+            button(text("<")).on_press(TimetableMsg::PreviousWeek),
+            text(format!("{} - {}", self.monday, sunday)),
+            button(text(">")).on_press(TimetableMsg::NextWeek),
+        ]
+        .push_maybe(self.week_note().map(text))
+        .spacing(PADDING)
+        .align_y(Vertical::Center);
+
+        let solver = row![
+            pick_list(
+                Preference::ALL,
+                Some(self.preference),
+                TimetableMsg::SelectPreference
+            ),
+            button(text("Find groups")).on_press_maybe(
+                (self.semester.is_some() && !self.solving).then_some(TimetableMsg::Solve)
+            ),
+        ]
+        .push_maybe(self.solving.then(|| text("Searching...")))
+        .spacing(PADDING)
+        .align_y(Vertical::Center);
+
+        let solutions = match &self.solutions {
+            None => Column::new(),
+            Some(solutions) if solutions.is_empty() => {
+                column![text("Every combination of groups has a conflict.")]
+            }
+            Some(solutions) => {
+                Column::with_children(solutions.iter().enumerate().map(|(index, solution)| {
+                    row![
+                        button(text("Apply")).on_press(TimetableMsg::Apply(index)),
+                        text(format!("{}. {}", index + 1, solution.cost)),
+                        text(self.describe(solution)),
+                    ]
+                    .spacing(PADDING)
+                    .align_y(Vertical::Center)
+                    .into()
+                }))
+            }
+        }
+        .spacing(PADDING);
+
+        column![
+            header,
+            solver,
+            solutions,
+            canvas(WeekGrid {
+                blocks: self.blocks(),
+                monday: self.monday,
+                holidays: (0..7)
+                    .map(|day| {
+                        let date = self.monday + Duration::days(day);
+                        self.calendar
+                            .holiday(date)
+                            .map(|holiday| holiday.name.clone())
+                    })
+                    .collect(),
+            })
+            .width(Fill)
+            .height(Fill),
+        ]
+        .spacing(PADDING)
+        .padding(PADDING)
+    }
+
+    fn title(&self) -> String {
+        match &self.semester {
+            Some(semester) => format!("Timetable - {semester}"),
+            None => "Timetable".to_string(),
+        }
+    }
+}
+
+/// The week grid, drawn on a canvas.
+struct WeekGrid {
+    blocks: Vec<Block>,
+    monday: Date,
+    /// The name of the holiday of each day of the week, if any.
+    holidays: Vec<Option<String>>,
+}
+
+impl WeekGrid {
+    /// The days shown: the weekend only appears if something happens on it.
+    fn days(&self) -> u8 {
+        let weekend = self
+            .blocks
+            .iter()
+            .any(|block| matches!(block.start.weekday(), Weekday::Saturday | Weekday::Sunday));
+        if weekend { 7 } else { 5 }
+    }
+
+    /// The first and last hours shown.
+    fn hours(&self) -> (u8, u8) {
+        let first = self.blocks.iter().map(|block| block.start.hour()).min();
+        let last = self
+            .blocks
+            .iter()
+            .map(|block| block.stop.hour() + u8::from(block.stop.minute() > 0))
+            .max();
+        (first.unwrap_or(8).min(8), last.unwrap_or(18).clamp(18, 24))
+    }
+
+    /// Where each block is drawn.
+    fn rectangles(&self, size: Size) -> Vec<Rectangle> {
+        let days = self.days();
+        let (first, last) = self.hours();
+        let day_width = (size.width - HOUR_LABEL_WIDTH) / days as f32;
+        let hour_height = (size.height - DAY_LABEL_HEIGHT) / (last - first) as f32;
+        let y = |time: PrimitiveDateTime| {
+            let hours = time.hour() as f32 + time.minute() as f32 / 60. - first as f32;
+            DAY_LABEL_HEIGHT + hours * hour_height
+        };
+
+        self.blocks
+            .iter()
+            .map(|block| {
+                let day = block.start.weekday().number_days_from_monday();
+                let lanes = self
+                    .blocks
+                    .iter()
+                    .filter(|other| other.start < block.stop && block.start < other.stop)
+                    .map(|other| other.lane + 1)
+                    .max()
+                    .unwrap_or(1);
+                let lane_width = (day_width - 4.) / lanes as f32;
+                let stop = if block.stop.date() == block.start.date() {
+                    y(block.stop)
+                } else {
+                    size.height
+                };
+                Rectangle {
+                    x: HOUR_LABEL_WIDTH
+                        + day as f32 * day_width
+                        + 2.
+                        + block.lane as f32 * lane_width,
+                    y: y(block.start),
+                    width: lane_width,
+                    height: (stop - y(block.start)).max(TEXT_SIZE),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Cuts a text so that it fits in the given width.
+fn fit(text: &str, width: f32) -> String {
+    let max = (width / (TEXT_SIZE * 0.6)).max(1.) as usize;
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut res: String = text.chars().take(max.saturating_sub(1)).collect();
+        res.push('…');
+        res
+    }
+}
+
+impl canvas::Program<TimetableMsg> for WeekGrid {
+    type State = ();
+
+    fn update(
+        &self,
+        _state: &mut Self::State,
+        event: canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> (event::Status, Option<TimetableMsg>) {
+        let canvas::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event else {
+            return (event::Status::Ignored, None);
+        };
+        let Some(position) = cursor.position_in(bounds) else {
+            return (event::Status::Ignored, None);
+        };
+
+        let clicked = self
+            .rectangles(bounds.size())
+            .into_iter()
+            .zip(&self.blocks)
+            .find(|(rectangle, _)| rectangle.contains(position));
+        match clicked {
+            Some((_, block)) => (
+                event::Status::Captured,
+                Some(TimetableMsg::Open(block.module)),
+            ),
+            None => (event::Status::Ignored, None),
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let palette = theme.extended_palette();
+        let line_color = palette.background.strong.color;
+        let text_color = palette.background.base.text;
+
+        let days = self.days();
+        let (first, last) = self.hours();
+        let day_width = (frame.width() - HOUR_LABEL_WIDTH) / days as f32;
+        let hour_height = (frame.height() - DAY_LABEL_HEIGHT) / (last - first) as f32;
+
+        for day in 0..days {
+            let x = HOUR_LABEL_WIDTH + day as f32 * day_width;
+            let date = self.monday + Duration::days(day as i64);
+            let mut label = format!(
+                "{} {}.{}.",
+                &date.weekday().to_string()[..3],
+                date.day(),
+                date.month() as u8
+            );
+            if let Some(Some(holiday)) = self.holidays.get(day as usize) {
+                frame.fill_rectangle(
+                    Point::new(x, 0.),
+                    Size::new(day_width, frame.height()),
+                    INACTIVE_COLOR,
+                );
+                label = format!("{label} {holiday}");
+            }
+            frame.fill_text(canvas::Text {
+                content: fit(&label, day_width),
+                position: Point::new(x + day_width / 2., DAY_LABEL_HEIGHT / 2.),
+                color: text_color,
+                size: (TEXT_SIZE + 2.).into(),
+                horizontal_alignment: Horizontal::Center,
+                vertical_alignment: Vertical::Center,
+                ..canvas::Text::default()
+            });
+            frame.stroke(
+                &CanvasPath::line(Point::new(x, 0.), Point::new(x, frame.height())),
+                Stroke::default().with_color(line_color),
+            );
+        }
+
+        for hour in first..last {
+            let y = DAY_LABEL_HEIGHT + (hour - first) as f32 * hour_height;
+            frame.fill_text(canvas::Text {
+                content: format!("{hour}:00"),
+                position: Point::new(HOUR_LABEL_WIDTH - 5., y),
+                color: text_color,
+                size: TEXT_SIZE.into(),
+                horizontal_alignment: Horizontal::Right,
+                ..canvas::Text::default()
+            });
+            frame.stroke(
+                &CanvasPath::line(
+                    Point::new(HOUR_LABEL_WIDTH, y),
+                    Point::new(frame.width(), y),
+                ),
+                Stroke::default().with_color(line_color),
+            );
+        }
+
+        for (rectangle, block) in self.rectangles(frame.size()).into_iter().zip(&self.blocks) {
+            let path = CanvasPath::rectangle(rectangle.position(), rectangle.size());
+            let color = if block.holiday {
+                Color {
+                    a: 0.4,
+                    ..block.color
+                }
+            } else {
+                block.color
+            };
+            frame.fill(&path, color);
+            if block.conflict {
+                frame.stroke(
+                    &path,
+                    Stroke::default().with_color(CONFLICT_COLOR).with_width(3.),
+                );
+            }
+
+            let lines = [
+                format!(
+                    "{:02}:{:02} - {:02}:{:02}",
+                    block.start.hour(),
+                    block.start.minute(),
+                    block.stop.hour(),
+                    block.stop.minute()
+                ),
+                block.title.clone(),
+                block.place.clone(),
+            ];
+            for (index, line) in lines.iter().enumerate() {
+                let y = rectangle.y + 2. + index as f32 * (TEXT_SIZE + 2.);
+                if y + TEXT_SIZE > rectangle.y + rectangle.height {
+                    break;
+                }
+                frame.fill_text(canvas::Text {
+                    content: fit(line, rectangle.width - 4.),
+                    position: Point::new(rectangle.x + 2., y),
+                    color: Color::BLACK,
+                    size: TEXT_SIZE.into(),
+                    ..canvas::Text::default()
+                });
+            }
+        }
+
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        let hovered = cursor.position_in(bounds).is_some_and(|position| {
+            self.rectangles(bounds.size())
+                .iter()
+                .any(|rectangle| rectangle.contains(position))
+        });
+        if hovered {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! Detection of appointments that take place at the same time.
+//!
+//! The appointments are given with an owner of any type, so that the same check can be used
+//! with [Owner]s, modules of a tree, or anything else.
+
+use std::fmt::Display;
+
+use time::{Duration, PrimitiveDateTime};
+
+use crate::module::{appointment::Appointment, course::Course, exam::Exam};
+
+/// Two appointments of different owners that overlap.
+#[derive(Debug, Clone)]
+pub struct Conflict<'a, K> {
+    /// The appointment that starts first, with its owner.
+    pub first: (K, &'a Appointment),
+    /// The other appointment, with its owner.
+    pub second: (K, &'a Appointment),
+    /// Start of the overlap.
+    pub start: PrimitiveDateTime,
+    /// End of the overlap.
+    pub stop: PrimitiveDateTime,
+}
+
+impl<K> Conflict<'_, K> {
+    /// How long both appointments overlap.
+    pub fn duration(&self) -> Duration {
+        self.stop - self.start
+    }
+
+    /// The places of both appointments.
+    pub fn places(&self) -> (&String, &String) {
+        (self.first.1.get_place(), self.second.1.get_place())
+    }
+}
+
+/// Finds every pair of overlapping appointments that have different owners.
+///
+/// The conflicts are sorted by the start of their first appointment.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::{conflict::find_conflicts, module::appointment::Appointment};
+/// use time::{Date, Month, Time};
+///
+/// let date = Date::from_calendar_date(2025, Month::October, 15).unwrap();
+/// let at = |hour| date.with_time(Time::from_hms(hour, 0, 0).unwrap());
+///
+/// let lecture = Appointment::new(at(10), at(12), "MW 2050".to_string());
+/// let tutorial = Appointment::new(at(11), at(13), "MW 1801".to_string());
+/// let lab = Appointment::new(at(13), at(15), "MW 0001".to_string());
+///
+/// let conflicts = find_conflicts([("lecture", &lecture), ("tutorial", &tutorial), ("lab", &lab)]);
+///
+/// assert_eq!(conflicts.len(), 1);
+/// assert_eq!(conflicts[0].first.0, "lecture");
+/// assert_eq!(conflicts[0].second.0, "tutorial");
+/// assert_eq!((conflicts[0].start, conflicts[0].stop), (at(11), at(12)));
+/// ```
+pub fn find_conflicts<'a, K: Clone + PartialEq>(
+    appointments: impl IntoIterator<Item = (K, &'a Appointment)>,
+) -> Vec<Conflict<'a, K>> {
+    let mut appointments: Vec<(K, &Appointment)> = appointments.into_iter().collect();
+    appointments
+        .sort_by_key(|(_, appointment)| (*appointment.get_start(), *appointment.get_stop()));
+
+    let mut conflicts = Vec::new();
+    for (index, (owner, appointment)) in appointments.iter().enumerate() {
+        // Sorted by start: only the following appointments that start before this one ends can overlap.
+        for (other_owner, other) in appointments[index + 1..]
+            .iter()
+            .take_while(|(_, other)| other.get_start() < appointment.get_stop())
+        {
+            if owner == other_owner {
+                continue;
+            }
+            if let Some((start, stop)) = appointment.overlap(other) {
+                conflicts.push(Conflict {
+                    first: (owner.clone(), *appointment),
+                    second: (other_owner.clone(), *other),
+                    start,
+                    stop,
+                })
+            }
+        }
+    }
+    conflicts
+}
+
+/// What an appointment belongs to: a group of a course, or an exam.
+#[derive(Debug, Clone, Copy)]
+pub enum Owner<'a> {
+    /// A group of a course.
+    Course {
+        /// The course.
+        course: &'a Course,
+        /// Name of the group in the [dates](Course::get_dates) of the course.
+        group: &'a str,
+    },
+    /// An exam.
+    Exam(&'a Exam),
+}
+
+/// Two owners are equal if they are the same course group or the same exam, not a copy.
+impl PartialEq for Owner<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Owner::Course { course, group },
+                Owner::Course {
+                    course: other_course,
+                    group: other_group,
+                },
+            ) => std::ptr::eq(*course, *other_course) && group == other_group,
+            (Owner::Exam(exam), Owner::Exam(other)) => std::ptr::eq(*exam, *other),
+            _ => false,
+        }
+    }
+}
+
+impl Display for Owner<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Owner::Course { course, group } => write!(f, "{} ({group})", course.get_title()),
+            Owner::Exam(exam) => write!(f, "Exam: {}", exam.get_title()),
+        }
+    }
+}
+
+/// Lists the appointments of the chosen groups of the courses, and the dates of the exams,
+/// with their [Owner]. The result can be given to [find_conflicts].
+///
+/// `groups` tells whether a group of a course is chosen.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::{conflict::*, module::exam::Exam};
+/// use std::io::Cursor;
+///
+/// let exam = |title: &str, time: &str| Exam::from_reader(Cursor::new(format!(
+/// "Title
+/// {title}
+/// Dates
+/// 20Feb 2026
+/// Friday, {time}
+/// MW 0001
+/// ")));
+/// let exams = [
+///     exam("Flight System Dynamics", "08:00 - 09:30"),
+///     exam("Aircraft Design", "09:00 - 10:30"),
+///     exam("Space Propulsion", "11:00 - 12:00"),
+/// ];
+///
+/// let conflicts = find_conflicts(owned_appointments([], &exams, |_, _| true));
+///
+/// assert_eq!(conflicts.len(), 1);
+/// assert_eq!(conflicts[0].first.0.to_string(), "Exam: Flight System Dynamics");
+/// assert_eq!(conflicts[0].second.0.to_string(), "Exam: Aircraft Design");
+/// assert_eq!(conflicts[0].duration(), time::Duration::minutes(30));
+/// ```
+pub fn owned_appointments<'a>(
+    courses: impl IntoIterator<Item = &'a Course>,
+    exams: impl IntoIterator<Item = &'a Exam>,
+    groups: impl Fn(&Course, &str) -> bool,
+) -> Vec<(Owner<'a>, &'a Appointment)> {
+    let mut res = Vec::new();
+    for course in courses {
+        for (group, dates) in course.get_dates() {
+            if groups(course, group) {
+                res.extend(dates.iter().map(|appointment| {
+                    (
+                        Owner::Course {
+                            course,
+                            group: group.as_str(),
+                        },
+                        appointment,
+                    )
+                }))
+            }
+        }
+    }
+    for exam in exams {
+        res.extend(
+            exam.get_dates()
+                .iter()
+                .map(|appointment| (Owner::Exam(exam), appointment)),
+        );
+    }
+    res
+}
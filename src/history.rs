@@ -0,0 +1,110 @@
+//! Undo and redo, by keeping the states before each change.
+
+use std::time::{Duration, Instant};
+
+/// Changes of the same group closer than this are merged into one step.
+pub const GROUP_DELAY: Duration = Duration::from_secs(1);
+
+/// Number of steps that can be undone.
+pub const MAX_STEPS: usize = 100;
+
+/// History of the states of a `T`, to undo and redo changes.
+///
+/// Changes are given a group, of type `G`. Consecutive changes of the same group, each less than
+/// [GROUP_DELAY] after the previous one, are undone at once. This is how the keystrokes typed in a field
+/// become one step.
+#[derive(Debug, Clone)]
+pub struct History<T, G = ()> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    /// Group of the last change and when it happened.
+    last: Option<(G, Instant)>,
+}
+
+impl<T, G> Default for History<T, G> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last: None,
+        }
+    }
+}
+
+impl<T, G: PartialEq> History<T, G> {
+    /// Creates an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a change, and forgets the undone steps.
+    ///
+    /// `before` gives the state before the change. It is only called if the change starts a new step,
+    /// that is if `group` is [None] or is not the group of the last change.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::history::History;
+    ///
+    /// let mut history = History::new();
+    /// let mut name = String::new();
+    ///
+    /// // Two keystrokes in the name, then a separate change.
+    /// for c in ['a', 'b'] {
+    ///     history.record(Some("name"), || name.clone());
+    ///     name.push(c);
+    /// }
+    /// history.record(None, || name.clone());
+    /// name = "Flight".to_string();
+    ///
+    /// name = history.undo(name).unwrap();
+    /// assert_eq!(name, "ab");
+    /// name = history.undo(name).unwrap();
+    /// assert_eq!(name, "");
+    /// assert!(history.undo(name.clone()).is_none());
+    ///
+    /// name = history.redo(name).unwrap();
+    /// assert_eq!(name, "ab");
+    /// ```
+    pub fn record(&mut self, group: Option<G>, before: impl FnOnce() -> T) {
+        let now = Instant::now();
+        let continues = matches!(
+            (&self.last, &group),
+            (Some((last, at)), Some(group)) if last == group && now - *at < GROUP_DELAY
+        );
+        if !continues {
+            self.undo.push(before());
+            if self.undo.len() > MAX_STEPS {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.last = group.map(|group| (group, now));
+    }
+
+    /// Undoes the last step: takes the current state and returns the one to restore, if any.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        self.last = None;
+        Some(previous)
+    }
+
+    /// Redoes the last undone step: takes the current state and returns the one to restore, if any.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        self.last = None;
+        Some(next)
+    }
+
+    /// Indicates whether there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Indicates whether there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
@@ -0,0 +1,124 @@
+//! Calendar files in the [iCalendar](https://datatracker.ietf.org/doc/html/rfc5545) format.
+//!
+//! All the times of the app are local times in Munich, so the files use the [TIMEZONE] time zone.
+
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, Weekday};
+
+mod export;
+pub use export::*;
+
+mod import;
+pub use import::*;
+
+/// The extension used for calendar files.
+pub const EXTENSION: &str = "ics";
+
+/// The time zone of every time written in a calendar file.
+pub const TIMEZONE: &str = "Europe/Berlin";
+
+/// Description of the [TIMEZONE] time zone, with the daylight saving time rules used since 1996.
+const VTIMEZONE: &str = "BEGIN:VTIMEZONE
+TZID:Europe/Berlin
+BEGIN:DAYLIGHT
+TZOFFSETFROM:+0100
+TZOFFSETTO:+0200
+TZNAME:CEST
+DTSTART:19700329T020000
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU
+END:DAYLIGHT
+BEGIN:STANDARD
+TZOFFSETFROM:+0200
+TZOFFSETTO:+0100
+TZNAME:CET
+DTSTART:19701025T030000
+RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU
+END:STANDARD
+END:VTIMEZONE";
+
+/// Last sunday of the given month, at 01:00 UTC. This is when daylight saving time changes.
+fn switch_time(year: i32, month: Month) -> OffsetDateTime {
+    let mut date = Date::from_calendar_date(year, month, month.length(year))
+        .expect("the last day of a month exists");
+    while date.weekday() != Weekday::Sunday {
+        date = date.previous_day().expect("the month has a sunday");
+    }
+    date.with_time(Time::from_hms(1, 0, 0).expect("01:00 is a valid time"))
+        .assume_utc()
+}
+
+/// Converts a time to the local time of the [TIMEZONE] time zone.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::ics::to_local;
+/// use time::{Date, Month, Time};
+///
+/// let date = Date::from_calendar_date(2025, Month::October, 15).unwrap();
+/// let utc = date.with_time(Time::from_hms(13, 0, 0).unwrap()).assume_utc();
+/// assert_eq!(to_local(utc).time(), Time::from_hms(15, 0, 0).unwrap());
+///
+/// let date = Date::from_calendar_date(2025, Month::November, 5).unwrap();
+/// let utc = date.with_time(Time::from_hms(13, 0, 0).unwrap()).assume_utc();
+/// assert_eq!(to_local(utc).time(), Time::from_hms(14, 0, 0).unwrap());
+/// ```
+pub fn to_local(date_time: OffsetDateTime) -> PrimitiveDateTime {
+    let year = date_time.to_offset(UtcOffset::UTC).year();
+    let summer = switch_time(year, Month::March) <= date_time
+        && date_time < switch_time(year, Month::October);
+    let offset = UtcOffset::from_hms(if summer { 2 } else { 1 }, 0, 0)
+        .expect("the offsets of the time zone are valid");
+    let local = date_time.to_offset(offset);
+    PrimitiveDateTime::new(local.date(), local.time())
+}
+
+/// Writes a date as `YYYYMMDD`.
+fn format_date(date: Date) -> String {
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        date.month() as u8,
+        date.day()
+    )
+}
+
+/// Writes a date and time as `YYYYMMDDTHHMMSS`.
+fn format_date_time(date_time: PrimitiveDateTime) -> String {
+    format!(
+        "{}T{:02}{:02}{:02}",
+        format_date(date_time.date()),
+        date_time.hour(),
+        date_time.minute(),
+        date_time.second()
+    )
+}
+
+/// Escapes the characters that have a meaning in a text value.
+fn escape(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => res.push_str("\\\\"),
+            ';' => res.push_str("\\;"),
+            ',' => res.push_str("\\,"),
+            '\n' => res.push_str("\\n"),
+            '\r' => (),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Splits a content line in lines of at most 75 bytes, as required by the format.
+fn fold(line: &str) -> String {
+    let mut res = String::with_capacity(line.len() + line.len() / 74 * 3);
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            res.push_str("\r\n ");
+            length = 1;
+        }
+        res.push(c);
+        length += c.len_utf8();
+    }
+    res
+}
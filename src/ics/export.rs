@@ -0,0 +1,329 @@
+//! Writing appointments, exams and registration deadlines in a calendar file.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use time::OffsetDateTime;
+
+use crate::module::{
+    Module,
+    appointment::Appointment,
+    course::{Course, DEFAULT_GROUP},
+    exam::Exam,
+};
+
+use super::*;
+
+/// When an [Event] takes place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum When {
+    /// From a start to a stop time, in the [TIMEZONE] time zone.
+    Timed {
+        /// Start of the event.
+        start: PrimitiveDateTime,
+        /// End of the event.
+        stop: PrimitiveDateTime,
+    },
+    /// The whole day.
+    AllDay(Date),
+}
+
+/// An event of a [Calendar].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// Identifier of the event. Calendar apps use it to update an event imported twice.
+    pub uid: String,
+    /// Title of the event.
+    pub summary: String,
+    /// Where the event takes place.
+    pub location: String,
+    /// Longer description of the event.
+    pub description: String,
+    /// When the event takes place.
+    pub when: When,
+}
+
+/// A set of [Event]s that can be written to a calendar file.
+#[derive(Debug, Clone, Default)]
+pub struct Calendar {
+    events: Vec<Event>,
+}
+
+/// Builds a unique identifier from the given parts.
+///
+/// Every byte of the parts other than ASCII letters and digits is percent-encoded, so that
+/// different parts, such as `MW 2050-1` and `MW20501`, never give the same identifier.
+fn uid(parts: &[&str]) -> String {
+    let parts: Vec<String> = parts
+        .iter()
+        .map(|part| {
+            part.bytes()
+                .map(|byte| match byte {
+                    byte if byte.is_ascii_alphanumeric() => (byte as char).to_string(),
+                    byte => format!("%{byte:02X}"),
+                })
+                .collect()
+        })
+        .collect();
+    format!("{}@tum-module-picker", parts.join("-"))
+}
+
+impl Calendar {
+    /// Creates an empty calendar.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets a reference to the events of the calendar.
+    pub fn get_events(&self) -> &Vec<Event> {
+        &self.events
+    }
+
+    /// Adds an event to the calendar.
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event)
+    }
+
+    /// Adds the appointments of the chosen groups of every course of a module,
+    /// the dates of its exams and all the registration deadlines.
+    ///
+    /// `groups` tells whether a group of a course is chosen.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::{ics::*, module::{Module, course::Course}};
+    /// use std::io::Cursor;
+    ///
+    /// let mut module = Module::default();
+    /// module.id = "MW2138".to_string();
+    /// module.name = "Flight System Dynamics".to_string();
+    /// module.courses.push(Course::from_reader(Cursor::new(
+    /// "Title
+    /// Flight System Dynamics
+    /// Dates and groups
+    /// Group 1
+    /// 15Oct 2025
+    /// Wednesday, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// Group 2
+    /// 16Oct 2025
+    /// Thursday, 08:00 - 10:00
+    /// MW 1801, Ernst-Schmidt-Hörsaal (5508.01.801)
+    /// Registration end
+    /// 20.10.2025
+    /// ")));
+    ///
+    /// let mut calendar = Calendar::new();
+    /// calendar.add_module(&module, |_, group| group == "Group 1");
+    /// assert_eq!(calendar.get_events().len(), 2);
+    /// assert_eq!(
+    ///     calendar.get_events()[0].uid,
+    ///     "MW2138--Group%201-20251015T150000@tum-module-picker"
+    /// );
+    ///
+    /// let mut file = Vec::new();
+    /// calendar.to_writer(&mut file).unwrap();
+    /// let file = String::from_utf8(file).unwrap();
+    ///
+    /// assert!(file.contains("DTSTART;TZID=Europe/Berlin:20251015T150000\r\n"));
+    /// assert!(file.contains("LOCATION:MW 2050\\, Zeichen-/Hörsaal (5510.02.050)\r\n"));
+    /// assert!(file.contains("DTSTART;VALUE=DATE:20251020\r\n"));
+    /// assert!(!file.contains("20251016T080000"));
+    /// ```
+    pub fn add_module(&mut self, module: &Module, groups: impl Fn(&Course, &str) -> bool) {
+        for course in &module.courses {
+            self.add_course(module, course, |group| groups(course, group));
+        }
+        for exam in &module.exams {
+            self.add_exam(module, exam);
+        }
+    }
+
+    /// Adds the appointments of the chosen groups of a course and its registration deadlines.
+    pub fn add_course(&mut self, module: &Module, course: &Course, groups: impl Fn(&str) -> bool) {
+        let title = if course.get_title().is_empty() {
+            &module.name
+        } else {
+            course.get_title()
+        };
+
+        let mut names: Vec<&String> = course.get_dates().keys().collect();
+        names.sort();
+        for group in names.into_iter().filter(|group| groups(group)) {
+            let summary = if group == DEFAULT_GROUP {
+                title.clone()
+            } else {
+                format!("{title} ({group})")
+            };
+            for appointment in &course.get_dates()[group] {
+                self.add_appointment(
+                    appointment,
+                    summary.clone(),
+                    description(module),
+                    &[&module.id, course.get_number(), group],
+                );
+            }
+        }
+
+        self.add_registration(
+            module,
+            title,
+            &[&module.id, course.get_number()],
+            [
+                course.get_registration_start(),
+                course.get_registration_end(),
+                course.get_deregistration_start(),
+                course.get_deregistration_end(),
+            ],
+        );
+    }
+
+    /// Adds the dates of an exam and its registration deadlines.
+    pub fn add_exam(&mut self, module: &Module, exam: &Exam) {
+        let title = if exam.get_title().is_empty() {
+            &module.name
+        } else {
+            exam.get_title()
+        };
+
+        for appointment in exam.get_dates() {
+            self.add_appointment(
+                appointment,
+                format!("Exam: {title}"),
+                description(module),
+                &[&module.id, exam.get_number(), "exam"],
+            );
+        }
+
+        self.add_registration(
+            module,
+            &format!("exam {title}"),
+            &[&module.id, exam.get_number(), "exam"],
+            [
+                exam.get_registration_start(),
+                exam.get_registration_end(),
+                exam.get_deregistration_start(),
+                exam.get_deregistration_end(),
+            ],
+        );
+    }
+
+    fn add_appointment(
+        &mut self,
+        appointment: &Appointment,
+        summary: String,
+        description: String,
+        uid_parts: &[&str],
+    ) {
+        let start = format_date_time(*appointment.get_start());
+        self.add_event(Event {
+            uid: uid(&[uid_parts, &[&start]].concat()),
+            summary,
+            location: appointment.get_place().clone(),
+            description,
+            when: When::Timed {
+                start: *appointment.get_start(),
+                stop: *appointment.get_stop(),
+            },
+        })
+    }
+
+    /// Adds an all-day event for each registration date that is known.
+    ///
+    /// `dates` holds the registration start and end, then the deregistration start and end.
+    fn add_registration(
+        &mut self,
+        module: &Module,
+        title: &str,
+        uid_parts: &[&str],
+        dates: [&Option<Date>; 4],
+    ) {
+        const LABELS: [&str; 4] = [
+            "Registration opens",
+            "Registration deadline",
+            "Deregistration opens",
+            "Deregistration deadline",
+        ];
+
+        for (label, date) in LABELS.into_iter().zip(dates) {
+            if let Some(date) = date {
+                self.add_event(Event {
+                    uid: uid(&[uid_parts, &[label]].concat()),
+                    summary: format!("{label}: {title}"),
+                    location: String::new(),
+                    description: description(module),
+                    when: When::AllDay(*date),
+                })
+            }
+        }
+    }
+
+    /// Writes the calendar.
+    pub fn to_writer<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let stamp = format_date_time({
+            let now = OffsetDateTime::now_utc();
+            PrimitiveDateTime::new(now.date(), now.time().replace_millisecond(0).unwrap())
+        });
+
+        let mut line = |line: &str| write!(writer, "{}\r\n", fold(line));
+
+        line("BEGIN:VCALENDAR")?;
+        line("VERSION:2.0")?;
+        line("PRODID:-//TUM Module Picker//EN")?;
+        line("CALSCALE:GREGORIAN")?;
+        for timezone_line in VTIMEZONE.lines() {
+            line(timezone_line)?;
+        }
+
+        for event in &self.events {
+            line("BEGIN:VEVENT")?;
+            line(&format!("UID:{}", escape(&event.uid)))?;
+            line(&format!("DTSTAMP:{stamp}Z"))?;
+            match event.when {
+                When::Timed { start, stop } => {
+                    line(&format!(
+                        "DTSTART;TZID={TIMEZONE}:{}",
+                        format_date_time(start)
+                    ))?;
+                    line(&format!("DTEND;TZID={TIMEZONE}:{}", format_date_time(stop)))?;
+                }
+                When::AllDay(date) => {
+                    line(&format!("DTSTART;VALUE=DATE:{}", format_date(date)))?;
+                    line(&format!(
+                        "DTEND;VALUE=DATE:{}",
+                        format_date(date.next_day().unwrap_or(date))
+                    ))?;
+                }
+            }
+            line(&format!("SUMMARY:{}", escape(&event.summary)))?;
+            if !event.location.is_empty() {
+                line(&format!("LOCATION:{}", escape(&event.location)))?;
+            }
+            if !event.description.is_empty() {
+                line(&format!("DESCRIPTION:{}", escape(&event.description)))?;
+            }
+            line("END:VEVENT")?;
+        }
+
+        line("END:VCALENDAR")
+    }
+
+    /// Saves the calendar in the file at the given path, replacing its content.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.to_writer(&mut writer)?;
+        writer.flush()
+    }
+}
+
+/// Description of the events of a module.
+fn description(module: &Module) -> String {
+    match (module.id.is_empty(), module.name.is_empty()) {
+        (false, false) => format!("{} {}", module.id, module.name),
+        (false, true) => module.id.clone(),
+        _ => module.name.clone(),
+    }
+}
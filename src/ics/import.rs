@@ -0,0 +1,711 @@
+//! Reading the events of a calendar file as [Appointment]s.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+    sync::LazyLock,
+};
+
+use regex::Regex;
+
+use crate::module::{appointment::Appointment, course::Course};
+
+use super::*;
+
+/// Recurring events without end are expanded over this duration.
+const UNBOUNDED_RECURRENCE: Duration = Duration::days(366);
+
+/// Recurring events are cut after this number of occurrences.
+const MAX_OCCURRENCES: usize = 1000;
+
+/// Time zones following the same rules as the [TIMEZONE] time zone, whose times are read as they are.
+const SAME_TIMEZONES: [&str; 8] = [
+    TIMEZONE,
+    "Europe/Amsterdam",
+    "Europe/Brussels",
+    "Europe/Paris",
+    "Europe/Rome",
+    "Europe/Vienna",
+    "Europe/Zurich",
+    "W. Europe Standard Time",
+];
+
+/// Error returned when a calendar file can't be imported.
+#[derive(Debug)]
+pub enum ImportError {
+    /// The file could not be opened or read.
+    Io(io::Error),
+    /// A property of an event can't be read.
+    InvalidProperty {
+        /// Number of the line of the property, starting at 1.
+        line: usize,
+        /// Name of the property, such as `DTSTART`.
+        name: String,
+    },
+    /// An event has no start.
+    MissingStart {
+        /// Number of the line where the event begins, starting at 1.
+        line: usize,
+    },
+    /// A time is given in a time zone whose rules are not known.
+    UnknownTimeZone {
+        /// Number of the line of the time, starting at 1.
+        line: usize,
+        /// The `TZID` of the time.
+        time_zone: String,
+    },
+    /// A recurrence rule uses parts that are not supported.
+    UnsupportedRecurrence {
+        /// Number of the line of the rule, starting at 1.
+        line: usize,
+        /// The rule.
+        rule: String,
+    },
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::Io(err) => write!(f, "Can't read the file: {err}"),
+            ImportError::InvalidProperty { line, name } => {
+                write!(f, "Line {line}: can't read the {name} property")
+            }
+            ImportError::MissingStart { line } => {
+                write!(f, "Line {line}: the event has no start")
+            }
+            ImportError::UnknownTimeZone { line, time_zone } => {
+                write!(
+                    f,
+                    "Line {line}: the time zone '{time_zone}' is not supported"
+                )
+            }
+            ImportError::UnsupportedRecurrence { line, rule } => {
+                write!(
+                    f,
+                    "Line {line}: the recurrence rule '{rule}' is not supported"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// A content line, such as `DTSTART;TZID=Europe/Berlin:20251015T150000`.
+struct Property {
+    line: usize,
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    /// Splits a content line in its name, parameters and value.
+    fn parse(line: usize, text: &str) -> Option<Self> {
+        let mut in_quotes = false;
+        let colon = text.char_indices().find_map(|(index, c)| match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                None
+            }
+            ':' if !in_quotes => Some(index),
+            _ => None,
+        })?;
+
+        let mut parts = text[..colon].split(';');
+        let name = parts.next()?.to_uppercase();
+        let params = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| (key.to_uppercase(), value.trim_matches('"').to_string()))
+            .collect();
+
+        Some(Self {
+            line,
+            name,
+            params,
+            value: text[colon + 1..].to_string(),
+        })
+    }
+
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn invalid(&self) -> ImportError {
+        ImportError::InvalidProperty {
+            line: self.line,
+            name: self.name.clone(),
+        }
+    }
+
+    /// Reads the value as a text.
+    fn text(&self) -> String {
+        let mut res = String::with_capacity(self.value.len());
+        let mut chars = self.value.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('n' | 'N') => res.push('\n'),
+                    Some(c) => res.push(c),
+                    None => (),
+                },
+                c => res.push(c),
+            }
+        }
+        res
+    }
+
+    /// Reads the value as a list of dates or times, in the [TIMEZONE] time zone.
+    ///
+    /// Times with a `TZID` must be in a time zone with the same rules as [TIMEZONE], and are read as
+    /// they are.
+    fn date_times(&self) -> Result<Vec<DateOrTime>, ImportError> {
+        if let Some(time_zone) = self.param("TZID")
+            && !SAME_TIMEZONES.contains(&time_zone)
+        {
+            return Err(ImportError::UnknownTimeZone {
+                line: self.line,
+                time_zone: time_zone.to_string(),
+            });
+        }
+        self.value
+            .split(',')
+            .map(|value| {
+                if self.param("VALUE") == Some("DATE") {
+                    parse_date(value).map(DateOrTime::Date)
+                } else {
+                    parse_date_time(value).map(DateOrTime::Time)
+                }
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(|| self.invalid())
+    }
+
+    fn date_time(&self) -> Result<DateOrTime, ImportError> {
+        self.date_times()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| self.invalid())
+    }
+}
+
+/// A date, for all-day events, or a local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum DateOrTime {
+    Date(Date),
+    Time(PrimitiveDateTime),
+}
+
+impl DateOrTime {
+    fn date(&self) -> Date {
+        match self {
+            DateOrTime::Date(date) => *date,
+            DateOrTime::Time(time) => time.date(),
+        }
+    }
+}
+
+/// Reads a date written as `YYYYMMDD`.
+fn parse_date(value: &str) -> Option<Date> {
+    let value = value.trim();
+    if value.len() != 8 || !value.is_ascii() {
+        return None;
+    }
+    let month: u8 = value[4..6].parse().ok()?;
+    Date::from_calendar_date(
+        value[..4].parse().ok()?,
+        Month::try_from(month).ok()?,
+        value[6..8].parse().ok()?,
+    )
+    .ok()
+}
+
+/// Reads a time written as `YYYYMMDDTHHMMSS`, or `YYYYMMDDTHHMMSSZ` in UTC.
+fn parse_date_time(value: &str) -> Option<PrimitiveDateTime> {
+    let value = value.trim();
+    let (value, utc) = match value.strip_suffix('Z') {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    if value.len() != 15 || !value.is_ascii() || &value[8..9] != "T" {
+        return None;
+    }
+    let time = Time::from_hms(
+        value[9..11].parse().ok()?,
+        value[11..13].parse().ok()?,
+        value[13..15].parse().ok()?,
+    )
+    .ok()?;
+    let date_time = parse_date(&value[..8])?.with_time(time);
+
+    Some(if utc {
+        to_local(date_time.assume_utc())
+    } else {
+        date_time
+    })
+}
+
+/// Reads a duration such as `PT1H30M` or `P1D`.
+fn parse_duration(value: &str) -> Option<Duration> {
+    static REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^([+-])?P(?:(\d+)W)?(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+)S)?)?$")
+            .unwrap()
+    });
+
+    let captures = REGEX.captures(value.trim())?;
+    let part = |index: usize| {
+        captures
+            .get(index)
+            .map_or(Some(0), |m| m.as_str().parse::<i64>().ok())
+    };
+    let duration = Duration::weeks(part(2)?)
+        + Duration::days(part(3)?)
+        + Duration::hours(part(4)?)
+        + Duration::minutes(part(5)?)
+        + Duration::seconds(part(6)?);
+
+    Some(match captures.get(1).map(|m| m.as_str()) {
+        Some("-") => -duration,
+        _ => duration,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A recurrence rule (`RRULE`), limited to the parts that lecture series use.
+#[derive(Debug, Clone)]
+struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<PrimitiveDateTime>,
+    /// The end of the rule in UTC, if it is written in UTC.
+    utc_until: Option<PrimitiveDateTime>,
+    by_day: Vec<Weekday>,
+}
+
+impl Rule {
+    fn parse(property: &Property) -> Result<Self, ImportError> {
+        let unsupported = || ImportError::UnsupportedRecurrence {
+            line: property.line,
+            rule: property.value.clone(),
+        };
+
+        let mut frequency = None;
+        let mut rule = Rule {
+            frequency: Frequency::Weekly,
+            interval: 1,
+            count: None,
+            until: None,
+            utc_until: None,
+            by_day: Vec::new(),
+        };
+
+        for part in property.value.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(|| property.invalid())?;
+            match key.to_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(unsupported()),
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().map_err(|_| property.invalid())?,
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| property.invalid())?),
+                "UNTIL" => {
+                    rule.until = Some(
+                        parse_date_time(value)
+                            .or_else(|| parse_date(value).map(|date| date.with_time(Time::MAX)))
+                            .ok_or_else(|| property.invalid())?,
+                    );
+                    rule.utc_until = value.strip_suffix('Z').and_then(parse_date_time);
+                }
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(|day| parse_weekday(day).ok_or_else(unsupported))
+                        .collect::<Result<_, _>>()?
+                }
+                // Only changes the result of rules with an interval and several days per week.
+                "WKST" => (),
+                _ => return Err(unsupported()),
+            }
+        }
+
+        rule.frequency = frequency.ok_or_else(|| property.invalid())?;
+        if rule.interval == 0 || (!rule.by_day.is_empty() && rule.frequency != Frequency::Weekly) {
+            return Err(unsupported());
+        }
+        Ok(rule)
+    }
+
+    /// Lists the starts of all occurrences of an event starting at `start`.
+    ///
+    /// `utc` tells whether `start` is in UTC rather than in the [TIMEZONE] time zone.
+    fn occurrences(&self, start: PrimitiveDateTime, utc: bool) -> Vec<PrimitiveDateTime> {
+        let until = if utc {
+            self.utc_until.or(self.until)
+        } else {
+            self.until
+        };
+        let until = match (until, self.count) {
+            (Some(until), _) => until,
+            (None, Some(_)) => PrimitiveDateTime::MAX,
+            (None, None) => start
+                .checked_add(UNBOUNDED_RECURRENCE)
+                .unwrap_or(PrimitiveDateTime::MAX),
+        };
+
+        let mut days: Vec<i64> = self
+            .by_day
+            .iter()
+            .map(|day| day.number_days_from_monday() as i64)
+            .collect();
+        if days.is_empty() {
+            days.push(start.weekday().number_days_from_monday() as i64)
+        }
+        days.sort();
+        days.dedup();
+        let monday =
+            start.date() - Duration::days(start.weekday().number_days_from_monday() as i64);
+
+        let mut res = Vec::new();
+        for period in 0_i64.. {
+            // The first day of the period, if it is still a valid date.
+            let first_day = period
+                .checked_mul(self.interval as i64)
+                .and_then(|offset| match self.frequency {
+                    Frequency::Daily => add_days(start.date(), offset),
+                    Frequency::Weekly => add_days(monday, offset.checked_mul(7)?),
+                    Frequency::Monthly => {
+                        month_date(start.year(), start.month() as i64 - 1 + offset, 1)
+                    }
+                    Frequency::Yearly => {
+                        let months = offset.checked_mul(12)?;
+                        month_date(start.year(), start.month() as i64 - 1 + months, 1)
+                    }
+                });
+            let Some(first_day) = first_day.filter(|day| day.midnight() <= until) else {
+                return res;
+            };
+
+            let candidates: Vec<Option<PrimitiveDateTime>> = match self.frequency {
+                Frequency::Daily => vec![Some(first_day.with_time(start.time()))],
+                Frequency::Weekly => days
+                    .iter()
+                    .map(|day| add_days(first_day, *day).map(|date| date.with_time(start.time())))
+                    .collect(),
+                Frequency::Monthly | Frequency::Yearly => {
+                    let months = first_day.month() as i64 - 1;
+                    vec![
+                        month_date(first_day.year(), months, start.day())
+                            .map(|date| date.with_time(start.time())),
+                    ]
+                }
+            };
+
+            // Invalid dates, such as the 31st of a short month, give no candidate.
+            for candidate in candidates.into_iter().flatten() {
+                if candidate > until
+                    || res.len() >= MAX_OCCURRENCES
+                    || self.count.is_some_and(|count| res.len() >= count as usize)
+                {
+                    return res;
+                }
+                if candidate >= start {
+                    res.push(candidate);
+                }
+            }
+        }
+        res
+    }
+}
+
+/// Adds a number of days to a date, if the result is still a valid date.
+fn add_days(date: Date, days: i64) -> Option<Date> {
+    let day = (date.to_julian_day() as i64).checked_add(days)?;
+    Date::from_julian_day(day.try_into().ok()?).ok()
+}
+
+/// The date of the given day of a month, with months counted from 0 and possibly past the end of
+/// the year.
+fn month_date(year: i32, month: i64, day: u8) -> Option<Date> {
+    let year = (year as i64).checked_add(month.div_euclid(12))?;
+    let month = Month::try_from(month.rem_euclid(12) as u8 + 1).ok()?;
+    Date::from_calendar_date(year.try_into().ok()?, month, day).ok()
+}
+
+/// Reads a weekday written as `MO`, `TU`, etc.
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    Some(match day.trim().to_uppercase().as_str() {
+        "MO" => Weekday::Monday,
+        "TU" => Weekday::Tuesday,
+        "WE" => Weekday::Wednesday,
+        "TH" => Weekday::Thursday,
+        "FR" => Weekday::Friday,
+        "SA" => Weekday::Saturday,
+        "SU" => Weekday::Sunday,
+        _ => return None,
+    })
+}
+
+/// The properties of a `VEVENT` that matter to build appointments.
+#[derive(Default)]
+struct VEvent {
+    line: usize,
+    uid: String,
+    start: Option<DateOrTime>,
+    /// The start in UTC, if it is written in UTC.
+    utc_start: Option<PrimitiveDateTime>,
+    end: Option<DateOrTime>,
+    duration: Option<Duration>,
+    location: String,
+    rule: Option<Rule>,
+    extra: Vec<DateOrTime>,
+    excluded: HashSet<DateOrTime>,
+    recurrence_id: Option<DateOrTime>,
+    cancelled: bool,
+}
+
+impl VEvent {
+    fn set(&mut self, property: &Property) -> Result<(), ImportError> {
+        match property.name.as_str() {
+            "UID" => self.uid = property.text(),
+            "DTSTART" => {
+                self.start = Some(property.date_time()?);
+                self.utc_start = property
+                    .value
+                    .trim()
+                    .strip_suffix('Z')
+                    .and_then(parse_date_time);
+            }
+            "DTEND" => self.end = Some(property.date_time()?),
+            "DURATION" => {
+                self.duration =
+                    Some(parse_duration(&property.value).ok_or_else(|| property.invalid())?)
+            }
+            "LOCATION" => self.location = property.text(),
+            "RRULE" => self.rule = Some(Rule::parse(property)?),
+            "RDATE" => self.extra.extend(property.date_times()?),
+            "EXDATE" => self.excluded.extend(property.date_times()?),
+            "RECURRENCE-ID" => self.recurrence_id = Some(property.date_time()?),
+            "STATUS" => self.cancelled = property.value.eq_ignore_ascii_case("CANCELLED"),
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Builds the appointments of the event, leaving out the occurrences in `overridden`.
+    ///
+    /// All-day events are not appointments and give nothing.
+    fn appointments(
+        &self,
+        overridden: Option<&HashSet<DateOrTime>>,
+    ) -> Result<Vec<Appointment>, ImportError> {
+        let start = match self.start {
+            Some(DateOrTime::Time(start)) => start,
+            Some(DateOrTime::Date(_)) => return Ok(Vec::new()),
+            None => return Err(ImportError::MissingStart { line: self.line }),
+        };
+        let duration = match (self.end, self.duration) {
+            (Some(DateOrTime::Time(end)), _) => end - start,
+            (Some(DateOrTime::Date(_)), _) => return Ok(Vec::new()),
+            (None, Some(duration)) => duration,
+            (None, None) => Duration::ZERO,
+        };
+
+        let mut starts = match &self.rule {
+            Some(rule) if self.recurrence_id.is_none() => match self.utc_start {
+                // The occurrences keep the same time in UTC, not in Munich.
+                Some(utc_start) => rule
+                    .occurrences(utc_start, true)
+                    .into_iter()
+                    .map(|occurrence| to_local(occurrence.assume_utc()))
+                    .collect(),
+                None => rule.occurrences(start, false),
+            },
+            _ => vec![start],
+        };
+        starts.extend(self.extra.iter().map(|extra| match extra {
+            DateOrTime::Time(time) => *time,
+            DateOrTime::Date(date) => date.with_time(start.time()),
+        }));
+        starts.sort();
+        starts.dedup();
+
+        let skipped = |occurrence: &PrimitiveDateTime| {
+            [&self.excluded].into_iter().chain(overridden).any(|set| {
+                set.contains(&DateOrTime::Time(*occurrence))
+                    || set.contains(&DateOrTime::Date(occurrence.date()))
+            })
+        };
+
+        Ok(starts
+            .into_iter()
+            .filter(|occurrence| !skipped(occurrence))
+            .map(|occurrence| {
+                Appointment::from_duration(occurrence, duration, self.location.clone())
+            })
+            .collect())
+    }
+}
+
+/// Reads the events of a calendar file as appointments, sorted by start.
+///
+/// Recurring events are expanded, without the dates in their `EXDATE`s.
+/// Events without end date are expanded over a year.
+/// All-day and cancelled events are left out.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::ics::read_appointments;
+/// use std::io::Cursor;
+/// use time::{Date, Month, Time};
+///
+/// let file = Cursor::new(
+/// "BEGIN:VCALENDAR\r
+/// VERSION:2.0\r
+/// BEGIN:VEVENT\r
+/// UID:fsd-lecture\r
+/// DTSTART;TZID=Europe/Berlin:20251015T150000\r
+/// DTEND;TZID=Europe/Berlin:20251015T170000\r
+/// RRULE:FREQ=WEEKLY;COUNT=4\r
+/// EXDATE;TZID=Europe/Berlin:20251022T150000\r
+/// LOCATION:MW 2050\\, Zeichen-/Hörsaal\r
+/// END:VEVENT\r
+/// BEGIN:VEVENT\r
+/// UID:fsd-exam\r
+/// DTSTART:20260220T070000Z\r
+/// DURATION:PT1H30M\r
+/// LOCATION:MW 0001\r
+/// END:VEVENT\r
+/// END:VCALENDAR\r
+/// ");
+///
+/// let appointments = read_appointments(file).unwrap();
+/// assert_eq!(appointments.len(), 4);
+///
+/// let date = Date::from_calendar_date(2025, Month::October, 29).unwrap();
+/// assert_eq!(appointments[1].get_start(), &date.with_time(Time::from_hms(15, 0, 0).unwrap()));
+/// assert_eq!(appointments[1].get_place(), "MW 2050, Zeichen-/Hörsaal");
+///
+/// // Converted from UTC to winter time in Munich.
+/// let date = Date::from_calendar_date(2026, Month::February, 20).unwrap();
+/// assert_eq!(appointments[3].get_start(), &date.with_time(Time::from_hms(8, 0, 0).unwrap()));
+/// assert_eq!(appointments[3].get_stop(), &date.with_time(Time::from_hms(9, 30, 0).unwrap()));
+///
+/// // Rules stop at the last valid date, and times in other time zones are rejected.
+/// let event = |start: &str, rule: &str| {
+///     Cursor::new(format!("BEGIN:VEVENT\r\nDTSTART{start}\r\nRRULE:{rule}\r\nEND:VEVENT\r\n"))
+/// };
+/// let appointments = read_appointments(event(":20251015T150000", "FREQ=DAILY;INTERVAL=1000000;COUNT=10"));
+/// assert_eq!(appointments.unwrap().len(), 3);
+/// let appointments = read_appointments(event(":20251015T150000", "FREQ=YEARLY;INTERVAL=1000;COUNT=20"));
+/// assert_eq!(appointments.unwrap().len(), 8);
+/// assert!(read_appointments(event(";TZID=America/New_York:20251015T150000", "FREQ=DAILY")).is_err());
+/// ```
+pub fn read_appointments<R: BufRead>(reader: R) -> Result<Vec<Appointment>, ImportError> {
+    // Unfolds the lines first: a line starting with a space continues the previous one.
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim_end_matches('\r');
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some((_, last))) => last.push_str(rest),
+            _ => lines.push((index + 1, line.to_string())),
+        }
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<VEvent> = None;
+    // Depth of the components nested in the current event, such as alarms.
+    let mut nested = 0;
+    for (line, text) in &lines {
+        let Some(property) = Property::parse(*line, text) else {
+            continue;
+        };
+        match (property.name.as_str(), &mut current) {
+            ("BEGIN", None) if property.value.eq_ignore_ascii_case("VEVENT") => {
+                current = Some(VEvent {
+                    line: *line,
+                    ..Default::default()
+                })
+            }
+            ("BEGIN", Some(_)) => nested += 1,
+            ("END", Some(_)) if nested > 0 => nested -= 1,
+            ("END", Some(_)) => events.extend(current.take()),
+            (_, Some(event)) if nested == 0 => event.set(&property)?,
+            _ => (),
+        }
+    }
+
+    // Occurrences of a recurring event that are replaced by another event.
+    let mut overridden: HashMap<&str, HashSet<DateOrTime>> = HashMap::new();
+    for event in &events {
+        if let Some(recurrence_id) = event.recurrence_id {
+            let set = overridden.entry(event.uid.as_str()).or_default();
+            set.insert(recurrence_id);
+            set.insert(DateOrTime::Date(recurrence_id.date()));
+        }
+    }
+
+    let mut appointments = Vec::new();
+    for event in events.iter().filter(|event| !event.cancelled) {
+        let overridden = match event.recurrence_id {
+            None => overridden.get(event.uid.as_str()),
+            Some(_) => None,
+        };
+        appointments.extend(event.appointments(overridden)?);
+    }
+    appointments.sort_by_key(|appointment| *appointment.get_start());
+
+    Ok(appointments)
+}
+
+/// Opens the calendar file at the given path and reads its events as appointments.
+///
+/// See [read_appointments].
+pub fn load_appointments(path: impl AsRef<Path>) -> Result<Vec<Appointment>, ImportError> {
+    read_appointments(BufReader::new(File::open(path)?))
+}
+
+impl Course {
+    /// Adds the events of a calendar file to the given group of the course.
+    ///
+    /// The group is created if needed. Returns the number of appointments that were added.
+    /// See [read_appointments].
+    pub fn import_ics<R: BufRead>(
+        &mut self,
+        group: String,
+        reader: R,
+    ) -> Result<usize, ImportError> {
+        let appointments = read_appointments(reader)?;
+        let count = appointments.len();
+        self.get_mut_dates()
+            .entry(group)
+            .or_default()
+            .extend(appointments);
+        Ok(count)
+    }
+}
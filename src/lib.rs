@@ -11,4 +11,22 @@ pub mod lines;
 
 pub mod window_stack;
 
-pub mod module;
\ No newline at end of file
+pub mod module;
+
+pub mod project;
+
+pub mod ics;
+
+pub mod conflict;
+
+pub mod schedule;
+
+pub mod study_plan;
+
+pub mod requirements;
+
+pub mod history;
+
+pub mod search;
+
+pub mod academic_calendar;
\ No newline at end of file
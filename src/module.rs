@@ -7,6 +7,7 @@ use std::{
 };
 
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use time::{Date, Duration, Month, PrimitiveDateTime, Time};
 
 mod module;
@@ -22,6 +23,9 @@ use appointment::*;
 pub mod semester;
 use semester::*;
 
+mod page;
+use page::*;
+
 /// The type for a year.
 pub type Year = u16;
 /// The type for an amount of ECTS
@@ -0,0 +1,374 @@
+//! Parser reading the dates copied from a TUMonline course or exam page.
+
+use std::fmt::Display;
+
+use time::Weekday;
+
+use super::*;
+
+/// A problem found on a line while reading appointments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Number of the line, starting at 1.
+    pub line: usize,
+    /// What went wrong.
+    pub reason: Reason,
+}
+
+/// Reason of a [Diagnostic].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// The line looks like a date but its month is unknown.
+    UnknownMonth(String),
+    /// The line looks like a date that does not exist, such as the 31st of November.
+    InvalidDate,
+    /// A date is not followed by a start and end time.
+    MissingTime,
+    /// The line should hold a start and end time but they can't be read.
+    InvalidTime,
+    /// The weekday written next to the time does not match the date.
+    WeekdayMismatch {
+        /// The weekday of the date.
+        expected: Weekday,
+        /// The weekday that is written.
+        found: Weekday,
+    },
+    /// A date and time are not followed by a place. The appointment is kept with an empty place.
+    MissingPlace,
+    /// The line was expected to start a new appointment with a date. It is ignored.
+    UnexpectedLine,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.reason)
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::UnknownMonth(month) => write!(f, "Unknown month '{month}'"),
+            Reason::InvalidDate => write!(f, "This date does not exist"),
+            Reason::MissingTime => write!(f, "The date has no start and end time"),
+            Reason::InvalidTime => write!(f, "Can't read a start and end time"),
+            Reason::WeekdayMismatch { expected, found } => {
+                write!(f, "The date is a {expected}, not a {found}")
+            }
+            Reason::MissingPlace => write!(f, "The appointment has no place"),
+            Reason::UnexpectedLine => write!(f, "Expected a date, the line is ignored"),
+        }
+    }
+}
+
+/// Where the parser is in the current appointment.
+enum State {
+    /// Waiting for the date of the next appointment.
+    Date,
+    /// Waiting for the time. Holds the line number of the date.
+    Time(usize, Date),
+    /// Waiting for the place. Holds the line number of the time.
+    Place(usize, PrimitiveDateTime, PrimitiveDateTime),
+}
+
+impl Appointment {
+    /// Reads a succession of appointments and returns an iterator over these appointments.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::appointment::*;
+    /// use std::io::Cursor;
+    /// use time::Time;
+    /// use time::Month::{self, *};
+    /// use time::Date;
+    /// use time::PrimitiveDateTime;
+    ///
+    /// let input = Cursor::new(
+    /// "15Oct 2025
+    /// Wednesday, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// 22Oct 2025
+    /// Wednesday, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// 29Oct 2025
+    /// Wednesday, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// ");
+    /// let mut appointments = Appointment::from_reader(input);
+    ///
+    /// let app1 = appointments.next();
+    /// let app2 = appointments.next();
+    /// let app3 = appointments.next();
+    /// let app4 = appointments.next();
+    ///
+    /// fn date(year: i32, month: Month, day: u8, hour: u8, minute: u8) -> PrimitiveDateTime {
+    ///     Date::from_calendar_date(year, month, day).unwrap()
+    ///         .with_time(Time::from_hms(hour, minute, 00).unwrap())
+    /// }
+    ///
+    /// match (app1, app2, app3, app4) {
+    ///     (Some(app1), Some(app2), Some(app3), None) => {
+    ///         assert_eq!(app1.get_start(), &date(2025, October, 15, 15, 00));
+    ///         assert_eq!(app1.get_stop(),  &date(2025, October, 15, 17, 00));
+    ///         assert_eq!(app1.get_place(), "MW 2050, Zeichen-/Hörsaal (5510.02.050)");
+    ///
+    ///         assert_eq!(app2.get_start(), &date(2025, October, 22, 15, 00));
+    ///         assert_eq!(app2.get_stop(),  &date(2025, October, 22, 17, 00));
+    ///         assert_eq!(app2.get_place(), "MW 2050, Zeichen-/Hörsaal (5510.02.050)");
+    ///
+    ///         assert_eq!(app3.get_start(), &date(2025, October, 29, 15, 00));
+    ///         assert_eq!(app3.get_stop(),  &date(2025, October, 29, 17, 00));
+    ///         assert_eq!(app3.get_place(), "MW 2050, Zeichen-/Hörsaal (5510.02.050)");
+    ///     }
+    ///     _ => unreachable!("3 appointments and only 3")
+    /// }
+    /// ```
+    pub fn from_reader<T: BufRead>(reader: T) -> impl Iterator<Item = Self> {
+        Self::from_reader_with_diagnostics(reader).0.into_iter()
+    }
+
+    /// Same as [from_reader](Self::from_reader) but also returns a [Diagnostic] for every line
+    /// that could not be used, ordered by line.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::appointment::*;
+    /// use std::io::Cursor;
+    ///
+    /// let input = Cursor::new(
+    /// "5. November 2025
+    /// Mittwoch, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// 12Nov 2025
+    /// Donnerstag, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// 19Nvb 2025
+    /// Wednesday, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// 03Dez 2025
+    /// Mittwoch
+    /// ");
+    /// let (appointments, diagnostics) = Appointment::from_reader_with_diagnostics(input);
+    ///
+    /// assert_eq!(appointments.len(), 2);
+    /// assert_eq!(appointments[1].get_start().month(), time::Month::November);
+    ///
+    /// assert_eq!(diagnostics.len(), 6);
+    /// assert_eq!(diagnostics[0].line, 5);
+    /// assert!(matches!(diagnostics[0].reason, Reason::WeekdayMismatch { .. }));
+    /// assert_eq!(diagnostics[1], Diagnostic { line: 7, reason: Reason::UnknownMonth("Nvb".to_string()) });
+    /// assert_eq!(diagnostics[4], Diagnostic { line: 10, reason: Reason::MissingTime });
+    /// assert_eq!(diagnostics[5], Diagnostic { line: 11, reason: Reason::InvalidTime });
+    /// ```
+    pub fn from_reader_with_diagnostics<T: BufRead>(reader: T) -> (Vec<Self>, Vec<Diagnostic>) {
+        let mut appointments = Vec::new();
+        let mut diagnostics = Vec::new();
+        let mut state = State::Date;
+
+        let lines = reader.lines().map_while(Result::ok);
+        for (index, line) in lines.enumerate() {
+            let number = index + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            state = match state {
+                State::Date => match read_date(line) {
+                    Some(Ok(date)) => State::Time(number, date),
+                    Some(Err(reason)) => {
+                        diagnostics.push(Diagnostic {
+                            line: number,
+                            reason,
+                        });
+                        State::Date
+                    }
+                    None => {
+                        diagnostics.push(Diagnostic {
+                            line: number,
+                            reason: Reason::UnexpectedLine,
+                        });
+                        State::Date
+                    }
+                },
+                State::Time(date_line, date) => match read_times(line) {
+                    Some((start, stop)) => {
+                        if let Some(found) = parse_weekday(line)
+                            && found != date.weekday()
+                        {
+                            diagnostics.push(Diagnostic {
+                                line: number,
+                                reason: Reason::WeekdayMismatch {
+                                    expected: date.weekday(),
+                                    found,
+                                },
+                            });
+                        }
+                        State::Place(number, date.with_time(start), date.with_time(stop))
+                    }
+                    None => match read_date(line) {
+                        Some(Ok(next)) => {
+                            diagnostics.push(Diagnostic {
+                                line: date_line,
+                                reason: Reason::MissingTime,
+                            });
+                            State::Time(number, next)
+                        }
+                        _ => {
+                            diagnostics.push(Diagnostic {
+                                line: number,
+                                reason: Reason::InvalidTime,
+                            });
+                            State::Time(date_line, date)
+                        }
+                    },
+                },
+                State::Place(time_line, start, stop) => match read_date(line) {
+                    // A new appointment starts right after the time.
+                    Some(Ok(next)) => {
+                        diagnostics.push(Diagnostic {
+                            line: time_line,
+                            reason: Reason::MissingPlace,
+                        });
+                        appointments.push(Appointment::new(start, stop, String::new()));
+                        State::Time(number, next)
+                    }
+                    _ => {
+                        appointments.push(Appointment::new(start, stop, line.to_string()));
+                        State::Date
+                    }
+                },
+            };
+        }
+
+        match state {
+            State::Date => (),
+            State::Time(line, _) => diagnostics.push(Diagnostic {
+                line,
+                reason: Reason::MissingTime,
+            }),
+            State::Place(line, start, stop) => {
+                diagnostics.push(Diagnostic {
+                    line,
+                    reason: Reason::MissingPlace,
+                });
+                appointments.push(Appointment::new(start, stop, String::new()));
+            }
+        }
+
+        // A missing line is only noticed on the next one, so diagnostics can come out of order.
+        diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+        (appointments, diagnostics)
+    }
+}
+
+/// Finds the month named by the given word, in English or German, abbreviated or not.
+fn parse_month(word: &str) -> Option<Month> {
+    let word = word.to_lowercase();
+    let prefix: String = word.chars().take(3).collect();
+    Some(match prefix.as_str() {
+        "jan" | "jän" => Month::January,
+        "feb" => Month::February,
+        "mar" | "mär" | "mrz" | "mae" => Month::March,
+        "apr" => Month::April,
+        "may" | "mai" => Month::May,
+        "jun" => Month::June,
+        "jul" => Month::July,
+        "aug" => Month::August,
+        "sep" => Month::September,
+        "oct" | "okt" => Month::October,
+        "nov" => Month::November,
+        "dec" | "dez" => Month::December,
+        _ => return None,
+    })
+}
+
+/// Finds the weekday named at the start of the line, in English or German.
+fn parse_weekday(line: &str) -> Option<Weekday> {
+    static REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(\p{L}+)").unwrap());
+
+    let word = REGEX.captures(line)?[1].to_lowercase();
+    Some(match word.as_str() {
+        "monday" | "mon" | "montag" | "mo" => Weekday::Monday,
+        "tuesday" | "tue" | "dienstag" | "di" => Weekday::Tuesday,
+        "wednesday" | "wed" | "mittwoch" | "mi" => Weekday::Wednesday,
+        "thursday" | "thu" | "donnerstag" | "do" => Weekday::Thursday,
+        "friday" | "fri" | "freitag" | "fr" => Weekday::Friday,
+        "saturday" | "sat" | "samstag" | "sonnabend" | "sa" => Weekday::Saturday,
+        "sunday" | "sun" | "sonntag" | "so" => Weekday::Sunday,
+        _ => return None,
+    })
+}
+
+/// Reads the date on a line.
+///
+/// Returns [None] if the line does not look like a date at all.
+fn read_date(str: &str) -> Option<Result<Date, Reason>> {
+    static NAMED_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(\d{1,2})\.?\s*(\p{L}{3,})\.?\s*(\d{4})").unwrap());
+    static NUMERIC_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(\d{1,2})\.(\d{1,2})\.(\d{4})").unwrap());
+
+    let (day, month, year) = match NUMERIC_REGEX.captures(str) {
+        Some(captures) => {
+            let month = captures[2]
+                .parse::<u8>()
+                .ok()
+                .and_then(|m| Month::try_from(m).ok());
+            match month {
+                Some(month) => (
+                    captures.get(1).unwrap().as_str(),
+                    month,
+                    captures.get(3).unwrap().as_str(),
+                ),
+                None => return Some(Err(Reason::InvalidDate)),
+            }
+        }
+        None => {
+            let captures = NAMED_REGEX.captures(str)?;
+            match parse_month(&captures[2]) {
+                Some(month) => (
+                    captures.get(1).unwrap().as_str(),
+                    month,
+                    captures.get(3).unwrap().as_str(),
+                ),
+                None => return Some(Err(Reason::UnknownMonth(captures[2].to_string()))),
+            }
+        }
+    };
+
+    Some(
+        day.parse()
+            .ok()
+            .zip(year.parse().ok())
+            .and_then(|(day, year)| Date::from_calendar_date(year, month, day).ok())
+            .ok_or(Reason::InvalidDate),
+    )
+}
+
+/// Reads a start and end time such as `15:00 - 17:00`.
+fn read_times(line: &str) -> Option<(Time, Time)> {
+    static REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d{1,2}):(\d{2})").unwrap());
+
+    let mut times = REGEX.captures_iter(line).map(|captures| {
+        Time::from_hms(captures[1].parse().ok()?, captures[2].parse().ok()?, 0).ok()
+    });
+    Some((times.next()??, times.next()??))
+}
+
+/// Reads a date such as `15Oct 2025`, `15. Oktober 2025` or `15.10.2025`.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::module::appointment::parse_date;
+/// use time::{Date, Month};
+///
+/// let date = Date::from_calendar_date(2025, Month::October, 15).unwrap();
+/// assert_eq!(parse_date("15Oct 2025"), Some(date));
+/// assert_eq!(parse_date("15. Oktober 2025"), Some(date));
+/// assert_eq!(parse_date("Start: 15.10.2025"), Some(date));
+/// assert_eq!(parse_date("Wednesday, 15:00 - 17:00"), None);
+/// ```
+pub fn parse_date(str: &str) -> Option<Date> {
+    read_date(str)?.ok()
+}
@@ -0,0 +1,193 @@
+//! Grouping of appointments into recurring series, such as the weekly dates of a lecture.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use time::Weekday;
+
+use super::*;
+
+/// Appointments recurring at the same time and place, every week or every few weeks.
+///
+/// A single appointment that does not belong to a series is a series of its own, with an
+/// [interval](Series::interval) of 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Series {
+    #[allow(missing_docs)]
+    pub weekday: Weekday,
+    /// Time at which each appointment starts.
+    pub start: Time,
+    /// Time at which each appointment ends.
+    pub stop: Time,
+    #[allow(missing_docs)]
+    pub place: String,
+    /// Number of weeks between two appointments. 0 if there is only one.
+    pub interval: u32,
+    /// Date of the first appointment of the rhythm.
+    pub first: Date,
+    /// Date of the last appointment of the rhythm.
+    pub last: Date,
+    /// Dates of the rhythm, between the first and last one, without appointment.
+    pub missing: Vec<Date>,
+    /// Dates of appointments at the same time and place, but out of the rhythm.
+    pub extra: Vec<Date>,
+}
+
+/// Greatest common divisor.
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+impl Series {
+    /// Groups appointments into series, ordered by their first date.
+    ///
+    /// Appointments on the same weekday, at the same time and place, make a series. Its interval is
+    /// the largest number of weeks dividing the gaps between them, and the dates of that rhythm
+    /// without appointment are [missing](Series::missing). A single appointment at the time and
+    /// place of a series, but on another day, is an [extra](Series::extra) date of that series.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::appointment::{Appointment, Series};
+    /// use time::{Date, Duration, Month, PrimitiveDateTime, Time};
+    ///
+    /// let date = |year, month, day| Date::from_calendar_date(year, month, day).unwrap();
+    /// let lecture = |date: Date| {
+    ///     let start = PrimitiveDateTime::new(date, Time::from_hms(15, 0, 0).unwrap());
+    ///     Appointment::from_duration(start, Duration::hours(2), "MW 2050".to_string())
+    /// };
+    ///
+    /// // Every Wednesday of the winter semester, except during the Christmas break.
+    /// let mut appointments: Vec<Appointment> = (0..17)
+    ///     .map(|week| date(2025, Month::October, 15) + Duration::weeks(week))
+    ///     .filter(|day| day.month() != Month::December || day.day() < 20)
+    ///     .map(lecture)
+    ///     .collect();
+    /// appointments.push(lecture(date(2025, Month::December, 19)));
+    ///
+    /// let series = Series::of(&appointments);
+    /// assert_eq!(series.len(), 1);
+    /// assert_eq!(series[0].interval, 1);
+    /// assert_eq!(series[0].missing, vec![date(2025, Month::December, 24), date(2025, Month::December, 31)]);
+    /// assert_eq!(
+    ///     series[0].to_string(),
+    ///     "Wed 15:00–17:00 weekly, 15 Oct–4 Feb, MW 2050 (except 24 Dec, 31 Dec; also 19 Dec)"
+    /// );
+    /// ```
+    pub fn of(appointments: &[Appointment]) -> Vec<Series> {
+        type Key = (u8, Time, Time, String);
+        let mut groups: BTreeMap<Key, Vec<Date>> = BTreeMap::new();
+        for appointment in appointments {
+            let (start, stop) = (appointment.get_start(), appointment.get_stop());
+            let key = (
+                start.weekday().number_days_from_monday(),
+                start.time(),
+                stop.time(),
+                appointment.get_place().clone(),
+            );
+            groups.entry(key).or_default().push(start.date());
+        }
+
+        let mut series = Vec::new();
+        let mut singles = Vec::new();
+        for ((_, start, stop, place), mut dates) in groups {
+            dates.sort();
+            dates.dedup();
+            let (first, last) = (dates[0], dates[dates.len() - 1]);
+            let gaps = dates
+                .windows(2)
+                .map(|pair| ((pair[1] - pair[0]).whole_weeks()) as u32);
+            let interval = gaps.fold(0, gcd);
+            let missing = (0..)
+                .map(|step| first + Duration::weeks(step * interval as i64))
+                .take_while(|date| interval > 0 && *date <= last)
+                .filter(|date| dates.binary_search(date).is_err())
+                .collect();
+            let one = Series {
+                weekday: first.weekday(),
+                start,
+                stop,
+                place,
+                interval,
+                first,
+                last,
+                missing,
+                extra: Vec::new(),
+            };
+            if interval == 0 {
+                singles.push(one)
+            } else {
+                series.push(one)
+            }
+        }
+
+        for single in singles {
+            let same = |other: &&mut Series| {
+                other.start == single.start
+                    && other.stop == single.stop
+                    && other.place == single.place
+            };
+            // Prefer the series during which the appointment takes place.
+            let owner = series
+                .iter_mut()
+                .filter(same)
+                .min_by_key(|other| !(other.first <= single.first && single.first <= other.last));
+            match owner {
+                Some(owner) => owner.extra.push(single.first),
+                None => series.push(single),
+            }
+        }
+
+        for one in &mut series {
+            one.extra.sort();
+        }
+        series.sort_by_key(|one| (one.first, one.start));
+        series
+    }
+}
+
+/// Short form of a date, such as "15 Oct".
+fn short_date(date: Date) -> String {
+    format!("{} {}", date.day(), &date.month().to_string()[..3])
+}
+
+impl Display for Series {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:02}:{:02}–{:02}:{:02} ",
+            &self.weekday.to_string()[..3],
+            self.start.hour(),
+            self.start.minute(),
+            self.stop.hour(),
+            self.stop.minute()
+        )?;
+        match self.interval {
+            0 => write!(f, "once, {}", short_date(self.first))?,
+            1 => write!(f, "weekly, ")?,
+            weeks => write!(f, "every {weeks} weeks, ")?,
+        }
+        if self.interval > 0 {
+            write!(f, "{}–{}", short_date(self.first), short_date(self.last))?;
+        }
+        if !self.place.is_empty() {
+            write!(f, ", {}", self.place)?;
+        }
+
+        let list = |dates: &[Date]| {
+            dates
+                .iter()
+                .map(|date| short_date(*date))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let notes: Vec<String> = [("except", &self.missing), ("also", &self.extra)]
+            .into_iter()
+            .filter(|(_, dates)| !dates.is_empty())
+            .map(|(label, dates)| format!("{label} {}", list(dates)))
+            .collect();
+        if !notes.is_empty() {
+            write!(f, " ({})", notes.join("; "))?;
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,16 @@
 //! A course in a module.
 
+use std::io::Cursor;
+
+use crate::{lines::Lines, sectioned_lines::get_sections};
 
 use super::*;
 
+/// Name of the group holding the dates of a course that is not split in groups.
+pub const DEFAULT_GROUP: &str = "Standard group";
+
 /// A course.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Course {
     // Overview
     title: String,
@@ -18,7 +24,7 @@ pub struct Course {
     ects: ECTS,
 
     language: String,
-    offered_in: Semester,
+    offered_in: Option<Semester>,
     organisation: String,
 
     // Description
@@ -122,12 +128,12 @@ impl Course {
     pub fn get_mut_language(&mut self) -> &mut String {
         &mut self.language
     }
-    /// Gets a reference to the course's offered_in field.
-    pub fn get_offered_in(&self) -> &Semester {
+    /// Gets a reference to the course's offered_in field, if known.
+    pub fn get_offered_in(&self) -> &Option<Semester> {
         &self.offered_in
     }
     /// Gets a mutable reference to the course's offered_in field.
-    pub fn get_mut_offered_in(&mut self) -> &mut Semester {
+    pub fn get_mut_offered_in(&mut self) -> &mut Option<Semester> {
         &mut self.offered_in
     }
     /// Gets a reference to the course's organisation field.
@@ -220,3 +226,208 @@ impl Course {
         &mut self.deregistration_end
     }
 }
+
+/// A line that starts a new section of a course page.
+#[derive(Debug, Clone, Copy)]
+enum Header {
+    Title,
+    Number,
+    Head,
+    Lecturers,
+    Type,
+    SemesterWeeklyHours,
+    Ects,
+    Language,
+    OfferedIn,
+    Organisation,
+    Content,
+    PreviousKnowledgeExpected,
+    Objective,
+    TeachingAndLearningMethod,
+    CourseCriteriaAndRegistration,
+    RecomendedReading,
+    Note,
+    DatesAndGroups,
+    Registration(RegistrationHeader),
+}
+
+impl Header {
+    fn find(line: &str) -> Option<Self> {
+        use Header::*;
+        let line = line.trim().trim_end_matches(':');
+        Some(match line {
+            "Title" | "Course title" => Title,
+            "Number" | "Course number" => Number,
+            "Head" | "Head of course" => Head,
+            "Lecturers" | "Lecturer" | "Lecturer (assistant)" => Lecturers,
+            "Type" | "Course type" => Type,
+            "Semester weekly hours" | "Semester hours" | "SWS" => SemesterWeeklyHours,
+            "ECTS credits" | "ECTS" => Ects,
+            "Language" | "Language of instruction" => Language,
+            "Offered in" | "Semester" => OfferedIn,
+            "Organisation" => Organisation,
+            "Content" => Content,
+            "Previous knowledge expected" => PreviousKnowledgeExpected,
+            "Objective" => Objective,
+            "Teaching and learning method" | "Teaching and learning methods" => {
+                TeachingAndLearningMethod
+            }
+            "Course criteria & registration" | "Course criteria and registration" => {
+                CourseCriteriaAndRegistration
+            }
+            "Recommended reading" | "Recommended literature" => RecomendedReading,
+            "Note" | "Notes" => Note,
+            "Dates and groups" | "Dates" => DatesAndGroups,
+            _ => return RegistrationHeader::find(line).map(Registration),
+        })
+    }
+}
+
+/// Indicates whether a line of the dates of a course is the name of a group.
+fn is_group_heading(line: &str) -> bool {
+    static REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bgroup\b|gruppe").unwrap());
+    let line = line.trim();
+    REGEX.is_match(line) && parse_date(line).is_none() && !line.contains(':')
+}
+
+impl Course {
+    /// Creates a new course with given title and number. The other fields are empty.
+    pub fn new(title: String, number: String) -> Self {
+        Self {
+            title,
+            number,
+            ..Default::default()
+        }
+    }
+
+    /// Reads a course page copied from TUMonline.
+    ///
+    /// Fields that are not found are left empty.
+    /// The dates are split by group: a line containing `group` (or `Gruppe`) starts a new group.
+    /// If there is no such line, all the dates go in the [DEFAULT_GROUP].
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::course::*;
+    /// use std::io::Cursor;
+    /// use time::{Date, Month};
+    ///
+    /// let page = Cursor::new(
+    /// "Title
+    /// Flight System Dynamics
+    /// Number
+    /// 0000001234
+    /// Type
+    /// Lecture
+    /// Semester weekly hours
+    /// 2
+    /// Offered in
+    /// Winter semester 2025/26
+    /// Dates and groups
+    /// Group 1
+    /// 15Oct 2025
+    /// Wednesday, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// 22Oct 2025
+    /// Wednesday, 15:00 - 17:00
+    /// MW 2050, Zeichen-/Hörsaal (5510.02.050)
+    /// Group 2
+    /// 16Oct 2025
+    /// Thursday, 08:00 - 10:00
+    /// MW 1801, Ernst-Schmidt-Hörsaal (5508.01.801)
+    /// Registration period
+    /// 01.09.2025 - 20.10.2025
+    /// ");
+    ///
+    /// let course = Course::from_reader(page);
+    ///
+    /// assert_eq!(course.get_title(), "Flight System Dynamics");
+    /// assert_eq!(course.get_typ(), "Lecture");
+    /// assert_eq!(course.get_semester_weekly_hours().whole_hours(), 2);
+    /// assert_eq!(course.get_offered_in().as_ref().unwrap().to_string(), "winter semester 2025");
+    ///
+    /// assert_eq!(course.get_dates()["Group 1"].len(), 2);
+    /// assert_eq!(course.get_dates()["Group 2"].len(), 1);
+    ///
+    /// assert_eq!(
+    ///     course.get_registration_end(),
+    ///     &Some(Date::from_calendar_date(2025, Month::October, 20).unwrap())
+    /// );
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> Self {
+        let mut course = Self::default();
+
+        let lines = reader.lines().map_while(Result::ok);
+        let mut sections = get_sections(lines, &Header::find);
+
+        while let Some(header) = sections.next_section() {
+            use Header::*;
+            match header {
+                Title => course.title = first_line(&mut sections),
+                Number => course.number = first_line(&mut sections),
+                Head => course.head = first_line(&mut sections),
+                Lecturers => course.lecturers = sections.text().trim().to_string(),
+                Type => course.typ = first_line(&mut sections),
+                SemesterWeeklyHours => {
+                    if let Some(hours) = parse_weekly_hours(&first_line(&mut sections)) {
+                        course.semester_weekly_hours = hours
+                    }
+                }
+                Ects => {
+                    if let Ok(ects) = first_line(&mut sections).parse() {
+                        course.ects = ects
+                    }
+                }
+                Language => course.language = first_line(&mut sections),
+                OfferedIn => {
+                    if let Ok(semester) = first_line(&mut sections).parse() {
+                        course.offered_in = Some(semester)
+                    }
+                }
+                Organisation => course.organisation = first_line(&mut sections),
+                Content => course.content = sections.text().trim().to_string(),
+                PreviousKnowledgeExpected => {
+                    course.previous_knowledge_expected = sections.text().trim().to_string()
+                }
+                Objective => course.objective = sections.text().trim().to_string(),
+                TeachingAndLearningMethod => {
+                    course.teaching_and_learning_method = sections.text().trim().to_string()
+                }
+                CourseCriteriaAndRegistration => {
+                    course.course_criteria_and_registration = sections.text().trim().to_string()
+                }
+                RecomendedReading => course.recomended_reading = sections.text().trim().to_string(),
+                Note => course.note = sections.text().trim().to_string(),
+                DatesAndGroups => course.dates = read_groups(&mut sections),
+                Registration(header) => header.read(
+                    &mut sections,
+                    (&mut course.registration_start, &mut course.registration_end),
+                    (&mut course.deregistration_start, &mut course.deregistration_end),
+                ),
+            }
+        }
+
+        course
+    }
+}
+
+/// Reads the dates of a course, split by group.
+fn read_groups(lines: &mut impl Lines) -> HashMap<String, Vec<Appointment>> {
+    let lines: Vec<String> = lines.map(|line| line.to_string()).collect();
+    let mut groups: HashMap<String, Vec<Appointment>> = HashMap::new();
+
+    if !lines.iter().any(|line| is_group_heading(line)) {
+        let appointments = Appointment::from_reader(Cursor::new(lines.join("\n"))).collect();
+        groups.insert(DEFAULT_GROUP.to_string(), appointments);
+        return groups;
+    }
+
+    let find_group = |line: &str| is_group_heading(line).then(|| line.trim().to_string());
+    let mut sections = get_sections(lines.iter().map(String::as_str), &find_group);
+    while let Some(group) = sections.next_section() {
+        let appointments = Appointment::from_reader(Cursor::new(sections.text()));
+        groups.entry(group).or_default().extend(appointments);
+    }
+
+    groups
+}
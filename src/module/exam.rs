@@ -1,9 +1,13 @@
 //! A module exam.
 
+use std::io::Cursor;
+
+use crate::{lines::Lines, sectioned_lines::get_sections};
+
 use super::*;
 
 /// An exam.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Exam {
     // Overview
     title: String,
@@ -15,7 +19,7 @@ pub struct Exam {
     semester_weekly_hours: Duration,
     ects: ECTS,
 
-    offered_in: Semester,
+    offered_in: Option<Semester>,
     organisation: String,
 
     // Description
@@ -88,12 +92,12 @@ impl Exam {
     pub fn get_mut_ects(&mut self) -> &mut ECTS {
         &mut self.ects
     }
-    /// Gets a reference to the exam's offered_in field.
-    pub fn get_offered_in(&self) -> &Semester {
+    /// Gets a reference to the exam's offered_in field, if known.
+    pub fn get_offered_in(&self) -> &Option<Semester> {
         &self.offered_in
     }
     /// Gets a mutable reference to the exam's offered_in field.
-    pub fn get_mut_offered_in(&mut self) -> &mut Semester {
+    pub fn get_mut_offered_in(&mut self) -> &mut Option<Semester> {
         &mut self.offered_in
     }
     /// Gets a reference to the exam's organisation field.
@@ -161,3 +165,147 @@ impl Exam {
         &mut self.deregistration_end
     }
 }
+
+/// A line that starts a new section of an exam page.
+#[derive(Debug, Clone, Copy)]
+enum Header {
+    Title,
+    Number,
+    PersonsInvolved,
+    Type,
+    SemesterWeeklyHours,
+    Ects,
+    OfferedIn,
+    Organisation,
+    FurtherInformation,
+    AssessmentScheme,
+    Note,
+    Dates,
+    Registration(RegistrationHeader),
+}
+
+impl Header {
+    fn find(line: &str) -> Option<Self> {
+        use Header::*;
+        let line = line.trim().trim_end_matches(':');
+        Some(match line {
+            "Title" | "Exam title" => Title,
+            "Number" | "Exam number" => Number,
+            "Persons involved" | "Examiner" | "Examiners" => PersonsInvolved,
+            "Type" | "Exam type" | "Type of exam" => Type,
+            "Semester weekly hours" | "Semester hours" | "SWS" => SemesterWeeklyHours,
+            "ECTS credits" | "ECTS" => Ects,
+            "Offered in" | "Semester" => OfferedIn,
+            "Organisation" => Organisation,
+            "Further information" => FurtherInformation,
+            "Assessment scheme" | "Grading scheme" => AssessmentScheme,
+            "Note" | "Notes" => Note,
+            "Dates" | "Exam dates" | "Dates and groups" => Dates,
+            _ => return RegistrationHeader::find(line).map(Registration),
+        })
+    }
+}
+
+impl Exam {
+    /// Creates a new exam with given title and number. The other fields are empty.
+    pub fn new(title: String, number: String) -> Self {
+        Self {
+            title,
+            number,
+            ..Default::default()
+        }
+    }
+
+    /// Reads an exam page copied from TUMonline.
+    ///
+    /// Fields that are not found are left empty.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::exam::*;
+    /// use std::io::Cursor;
+    /// use time::{Date, Month};
+    ///
+    /// let page = Cursor::new(
+    /// "Title
+    /// Flight System Dynamics (written exam)
+    /// Number
+    /// 0000005678
+    /// Type
+    /// Written exam
+    /// Offered in
+    /// Winter semester 2025/26
+    /// Assessment scheme
+    /// 1,0 - 5,0
+    /// Dates
+    /// 20Feb 2026
+    /// Friday, 08:00 - 09:30
+    /// MW 0001, Gustav-Niemann-Hörsaal (5510.EG.001)
+    /// Registration start
+    /// 01.12.2025
+    /// Deregistration end
+    /// 13.02.2026
+    /// ");
+    ///
+    /// let exam = Exam::from_reader(page);
+    ///
+    /// assert_eq!(exam.get_title(), "Flight System Dynamics (written exam)");
+    /// assert_eq!(exam.get_typ(), "Written exam");
+    /// assert_eq!(exam.get_assessment_scheme(), "1,0 - 5,0");
+    /// assert_eq!(exam.get_offered_in().as_ref().unwrap().to_string(), "winter semester 2025");
+    /// assert_eq!(exam.get_dates().len(), 1);
+    /// assert_eq!(
+    ///     exam.get_registration_start(),
+    ///     &Some(Date::from_calendar_date(2025, Month::December, 1).unwrap())
+    /// );
+    /// assert_eq!(
+    ///     exam.get_deregistration_end(),
+    ///     &Some(Date::from_calendar_date(2026, Month::February, 13).unwrap())
+    /// );
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> Self {
+        let mut exam = Self::default();
+
+        let lines = reader.lines().map_while(Result::ok);
+        let mut sections = get_sections(lines, &Header::find);
+
+        while let Some(header) = sections.next_section() {
+            use Header::*;
+            match header {
+                Title => exam.title = first_line(&mut sections),
+                Number => exam.number = first_line(&mut sections),
+                PersonsInvolved => exam.persons_involved = sections.text().trim().to_string(),
+                Type => exam.typ = first_line(&mut sections),
+                SemesterWeeklyHours => {
+                    if let Some(hours) = parse_weekly_hours(&first_line(&mut sections)) {
+                        exam.semester_weekly_hours = hours
+                    }
+                }
+                Ects => {
+                    if let Ok(ects) = first_line(&mut sections).parse() {
+                        exam.ects = ects
+                    }
+                }
+                OfferedIn => {
+                    if let Ok(semester) = first_line(&mut sections).parse() {
+                        exam.offered_in = Some(semester)
+                    }
+                }
+                Organisation => exam.organisation = first_line(&mut sections),
+                FurtherInformation => exam.further_information = sections.text().trim().to_string(),
+                AssessmentScheme => exam.assessment_scheme = sections.text().trim().to_string(),
+                Note => exam.note = sections.text().trim().to_string(),
+                Dates => exam
+                    .dates
+                    .extend(Appointment::from_reader(Cursor::new(sections.text()))),
+                Registration(header) => header.read(
+                    &mut sections,
+                    (&mut exam.registration_start, &mut exam.registration_end),
+                    (&mut exam.deregistration_start, &mut exam.deregistration_end),
+                ),
+            }
+        }
+
+        exam
+    }
+}
@@ -3,8 +3,18 @@ use super::{course::*, exam::*, *};
 
 mod macros;
 
+mod parser;
+pub use parser::*;
+
+mod value;
+pub use value::*;
+
+mod diff;
+pub use diff::*;
+
 /// A module.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Module {
     // === Overview ===
     #[allow(missing_docs)]
@@ -86,7 +96,7 @@ pub struct Module {
 }
 
 /// Enum representing the different fields.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Field {
     // === Overview ===
     #[allow(missing_docs)]
@@ -160,6 +170,62 @@ pub enum Field {
     ResponsibleBis,
 }
 
+impl Field {
+    /// Every field, in the order of the [Module] struct.
+    pub const ALL: [Field; 31] = {
+        use Field::*;
+        [
+            Name,
+            Id,
+            ECTS,
+            Version,
+            ValidFrom,
+            ValidUntil,
+            Responsible,
+            Organisation,
+            Note,
+            Courses,
+            Exams,
+            ModuleLevel,
+            Abbreviation,
+            Subtitle,
+            Duration,
+            Occurence,
+            Language,
+            RelatedPrograms,
+            TotalHours,
+            ContactHours,
+            SelfStudyHours,
+            DescrOfAchievementAssessmentMethods,
+            ExamRetakeNextSemester,
+            ExamRetakeEndSemester,
+            Prerequisites,
+            IntendedLearningOutcomes,
+            Content,
+            TeachingAndLearningMethods,
+            Media,
+            ReadingList,
+            ResponsibleBis,
+        ]
+    };
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Field::*;
+        let name = match self {
+            ValidFrom => "Valid from",
+            ValidUntil => "Valid until",
+            Responsible => "Responsible",
+            Courses => "Courses",
+            Exams => "Exams",
+            ResponsibleBis => "Responsible for Module",
+            field => field.header().unwrap_or_default(),
+        };
+        write!(f, "{name}")
+    }
+}
+
 // Getters and mutable getters for Module
 impl Module {
     /// Gets a reference to the module's language field.
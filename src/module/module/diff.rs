@@ -0,0 +1,302 @@
+//! Differences between two versions of a [Module], and merging of some of them.
+
+use std::fmt::Display;
+
+use crate::schedule::course_key;
+
+use super::*;
+
+/// A change of a course or an exam.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemChange<T> {
+    #[allow(missing_docs)]
+    Added(T),
+    #[allow(missing_docs)]
+    Removed(T),
+    /// The item was paired with one of the same number, which differs.
+    Modified {
+        #[allow(missing_docs)]
+        old: T,
+        #[allow(missing_docs)]
+        new: T,
+    },
+}
+
+/// A difference between two versions of a module, see [Module::diff].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A field other than the courses and exams has a new value.
+    Value {
+        #[allow(missing_docs)]
+        field: Field,
+        #[allow(missing_docs)]
+        old: FieldValue,
+        #[allow(missing_docs)]
+        new: FieldValue,
+    },
+    #[allow(missing_docs)]
+    Course(Box<ItemChange<Course>>),
+    #[allow(missing_docs)]
+    Exam(Box<ItemChange<Exam>>),
+}
+
+/// Key by which the exams of two versions are matched: their number, or their title if they have
+/// none, like the [course_key] of courses.
+fn exam_key(exam: &Exam) -> &String {
+    if exam.get_number().is_empty() {
+        exam.get_title()
+    } else {
+        exam.get_number()
+    }
+}
+
+/// The changes from the old items to the new ones.
+///
+/// Each item is paired with at most one other: equal items first, then the remaining ones by key in
+/// order, so that several items with the same key are not all compared to the first of them.
+fn diff_items<T: Clone + PartialEq>(
+    old: &[T],
+    new: &[T],
+    key: impl Fn(&T) -> &String,
+) -> Vec<ItemChange<T>> {
+    let mut paired = vec![false; new.len()];
+    let unchanged: Vec<bool> = old
+        .iter()
+        .map(|item| pair(new, &mut paired, |other| other == item).is_some())
+        .collect();
+
+    let mut changes = Vec::new();
+    for (item, unchanged) in old.iter().zip(unchanged) {
+        if unchanged {
+            continue;
+        }
+        match pair(new, &mut paired, |other| key(other) == key(item)) {
+            None => changes.push(ItemChange::Removed(item.clone())),
+            Some(index) => changes.push(ItemChange::Modified {
+                old: item.clone(),
+                new: new[index].clone(),
+            }),
+        }
+    }
+    for (item, _) in new.iter().zip(paired).filter(|(_, paired)| !paired) {
+        changes.push(ItemChange::Added(item.clone()))
+    }
+    changes
+}
+
+/// Pairs the first item not paired yet that matches, and returns its position.
+fn pair<T>(items: &[T], paired: &mut [bool], matches: impl Fn(&T) -> bool) -> Option<usize> {
+    let index = (0..items.len()).find(|&index| !paired[index] && matches(&items[index]))?;
+    paired[index] = true;
+    Some(index)
+}
+
+/// Position of the item equal to the given one, or else of the first one with its key.
+fn find_item<T: PartialEq>(items: &[T], item: &T, key: impl Fn(&T) -> &String) -> Option<usize> {
+    items
+        .iter()
+        .position(|other| other == item)
+        .or_else(|| items.iter().position(|other| key(other) == key(item)))
+}
+
+/// Applies a change to the items.
+fn apply_item<T: Clone + PartialEq>(
+    items: &mut Vec<T>,
+    change: &ItemChange<T>,
+    key: impl Fn(&T) -> &String,
+) {
+    match change {
+        ItemChange::Added(item) => items.push(item.clone()),
+        ItemChange::Removed(item) => {
+            if let Some(index) = find_item(items, item, key) {
+                items.remove(index);
+            }
+        }
+        ItemChange::Modified { old, new } => match find_item(items, old, key) {
+            Some(index) => items[index] = new.clone(),
+            None => items.push(new.clone()),
+        },
+    }
+}
+
+impl Change {
+    /// The field that changed.
+    pub fn field(&self) -> Field {
+        match self {
+            Change::Value { field, .. } => *field,
+            Change::Course(_) => Field::Courses,
+            Change::Exam(_) => Field::Exams,
+        }
+    }
+
+    /// Applies the change to a module.
+    ///
+    /// A removed or modified course or exam is looked up as it was, or else by its number, so a
+    /// change can be applied to another version of the module than the one it was computed from.
+    pub fn apply(&self, module: &mut Module) {
+        match self {
+            Change::Value { field, new, .. } => {
+                // A change only holds values of the type of its field.
+                let _ = module.set(*field, new.clone());
+            }
+            Change::Course(change) => apply_item(&mut module.courses, change, course_key),
+            Change::Exam(change) => apply_item(&mut module.exams, change, exam_key),
+        }
+    }
+}
+
+impl Display for Change {
+    /// Short description of the change, such as `ECTS credits: 5 -> 6`. Long texts are not written out.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let item = |title: &String, number: &String| format!("{title} ({number})");
+        match self {
+            Change::Value { field, old, new } => {
+                let (old, new) = (old.to_string(), new.to_string());
+                if old.contains('\n') || new.contains('\n') {
+                    write!(f, "{field} changed")
+                } else {
+                    write!(f, "{field}: {old} -> {new}")
+                }
+            }
+            Change::Course(change) => match &**change {
+                ItemChange::Added(course) => write!(
+                    f,
+                    "Course added: {}",
+                    item(course.get_title(), course.get_number())
+                ),
+                ItemChange::Removed(course) => write!(
+                    f,
+                    "Course removed: {}",
+                    item(course.get_title(), course.get_number())
+                ),
+                ItemChange::Modified { new, .. } => write!(
+                    f,
+                    "Course changed: {}",
+                    item(new.get_title(), new.get_number())
+                ),
+            },
+            Change::Exam(change) => match &**change {
+                ItemChange::Added(exam) => {
+                    write!(
+                        f,
+                        "Exam added: {}",
+                        item(exam.get_title(), exam.get_number())
+                    )
+                }
+                ItemChange::Removed(exam) => {
+                    write!(
+                        f,
+                        "Exam removed: {}",
+                        item(exam.get_title(), exam.get_number())
+                    )
+                }
+                ItemChange::Modified { new, .. } => {
+                    write!(
+                        f,
+                        "Exam changed: {}",
+                        item(new.get_title(), new.get_number())
+                    )
+                }
+            },
+        }
+    }
+}
+
+impl Module {
+    /// The changes that turn this module into the other one, in the order of [Field::ALL].
+    ///
+    /// Courses and exams are paired one to one, by number or by title if they have none, so each of
+    /// them is added, removed or modified on its own, even when several share a number.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::{
+    ///     Change, Field, FieldValue, ItemChange, Module, course::Course, exam::Exam,
+    /// };
+    ///
+    /// let mut old = Module::default();
+    /// old.ects = 5;
+    /// old.version = "v1".to_string();
+    /// old.exams = vec![Exam::new("Written exam".to_string(), "EX1".to_string())];
+    ///
+    /// let mut new = old.clone();
+    /// new.ects = 6;
+    /// new.version = "v2".to_string();
+    /// new.courses = vec![Course::new("Lecture".to_string(), "C1".to_string())];
+    /// new.exams = vec![Exam::new("Oral exam".to_string(), "EX1".to_string())];
+    ///
+    /// let changes = old.diff(&new);
+    /// assert_eq!(changes.len(), 4);
+    /// assert_eq!(changes[0], Change::Value {
+    ///     field: Field::ECTS,
+    ///     old: FieldValue::ECTS(5),
+    ///     new: FieldValue::ECTS(6),
+    /// });
+    /// assert_eq!(changes[2].field(), Field::Courses);
+    /// assert_eq!(changes[2].to_string(), "Course added: Lecture (C1)");
+    /// assert_eq!(changes[3].to_string(), "Exam changed: Oral exam (EX1)");
+    /// assert_eq!(changes[0].to_string(), "ECTS credits: 5 -> 6");
+    ///
+    /// // Only take the new ECTS and the new exam.
+    /// let mut merged = old.clone();
+    /// merged.merge([&changes[0], &changes[3]]);
+    /// assert_eq!(merged.ects, 6);
+    /// assert_eq!(merged.version, "v1");
+    /// assert!(merged.courses.is_empty());
+    /// assert_eq!(merged.exams[0].get_title(), "Oral exam");
+    ///
+    /// merged.merge(&changes);
+    /// assert_eq!(merged, new);
+    ///
+    /// // Two exams without number and with the same title are each paired with one new exam.
+    /// let repeat = Exam::new("Repeat exam".to_string(), String::new());
+    /// old.exams = vec![repeat.clone(), repeat.clone()];
+    /// let mut new = old.clone();
+    /// *new.exams[1].get_mut_note() = "Room changed".to_string();
+    /// let changes = old.diff(&new);
+    /// assert_eq!(changes.len(), 1);
+    /// let Change::Exam(change) = &changes[0] else { panic!() };
+    /// assert!(matches!(**change, ItemChange::Modified { .. }));
+    ///
+    /// let mut new = old.clone();
+    /// new.exams.push(repeat.clone());
+    /// let changes = old.diff(&new);
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].to_string(), "Exam added: Repeat exam ()");
+    ///
+    /// let mut merged = old.clone();
+    /// merged.merge(&changes);
+    /// assert_eq!(merged, new);
+    /// ```
+    pub fn diff(&self, other: &Module) -> Vec<Change> {
+        let mut changes = Vec::new();
+        for field in Field::ALL {
+            match field {
+                Field::Courses => changes.extend(
+                    diff_items(&self.courses, &other.courses, course_key)
+                        .into_iter()
+                        .map(|change| Change::Course(Box::new(change))),
+                ),
+                Field::Exams => changes.extend(
+                    diff_items(&self.exams, &other.exams, exam_key)
+                        .into_iter()
+                        .map(|change| Change::Exam(Box::new(change))),
+                ),
+                field => {
+                    let (old, new) = (self.get(field), other.get(field));
+                    if old != new {
+                        changes.push(Change::Value { field, old, new })
+                    }
+                }
+            }
+        }
+        changes
+    }
+
+    /// Applies the chosen changes, usually a subset of a [diff](Module::diff).
+    pub fn merge<'a>(&mut self, changes: impl IntoIterator<Item = &'a Change>) {
+        for change in changes {
+            change.apply(self)
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Parser reading a whole module page copied from TUMonline.
+
+use std::{fmt::Display, num::ParseIntError};
+
+use crate::{lines::Lines, sectioned_lines::get_sections};
+
+use super::*;
+
+/// Result of [Module::from_reader], telling what happened to each [Field].
+#[derive(Debug, Clone, Default)]
+pub struct ParseReport {
+    /// Fields that were found and read.
+    pub found: Vec<Field>,
+    /// Fields that have a [header](Field::header) but were not found in the text.
+    pub missing: Vec<Field>,
+    /// Fields that were found but could not be read, with the reason why.
+    pub unparseable: Vec<(Field, FieldError)>,
+}
+
+impl ParseReport {
+    /// Indicates whether every field with a [header](Field::header) was found and read.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty() && self.unparseable.is_empty()
+    }
+}
+
+/// Error explaining why a field could not be read.
+#[derive(Debug, Clone)]
+pub enum FieldError {
+    /// The value should be a whole number.
+    Number(ParseIntError),
+    /// The value should be a semester.
+    Semester(semester::ParseError),
+    /// The value should be yes or no.
+    Bool,
+    /// The field holds courses or exams, which can't be read from a single value.
+    NotText,
+}
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldError::Number(err) => write!(f, "Can't read a number: {err}"),
+            FieldError::Semester(err) => write!(f, "{err}"),
+            FieldError::Bool => write!(f, "Can't read a boolean. Write 'yes' or 'no'."),
+            FieldError::NotText => write!(f, "Courses and exams can't be written as text"),
+        }
+    }
+}
+
+impl Field {
+    /// The header introducing the field in a TUMonline module page.
+    ///
+    /// Returns [None] for fields that are not on the module page.
+    /// [ValidFrom](Field::ValidFrom) and [ValidUntil](Field::ValidUntil) share the same header,
+    /// as well as [Responsible](Field::Responsible) and [ResponsibleBis](Field::ResponsibleBis).
+    pub fn header(&self) -> Option<&'static str> {
+        use Field::*;
+        Some(match self {
+            Name => "Name",
+            Id => "Module ID",
+            ECTS => "ECTS credits",
+            Version => "Version",
+            ValidFrom | ValidUntil => "Valid",
+            Responsible | ResponsibleBis => "Responsible for Module",
+            Organisation => "Organisation",
+            Note => "Note",
+            Courses | Exams => return None,
+            ModuleLevel => "Module Level",
+            Abbreviation => "Abbreviation",
+            Subtitle => "Subtitle",
+            Duration => "Duration",
+            Occurence => "Occurrence",
+            Language => "Language",
+            RelatedPrograms => "Related Programs",
+            TotalHours => "Total Hours",
+            ContactHours => "Contact Hours",
+            SelfStudyHours => "Self-study Hours",
+            DescrOfAchievementAssessmentMethods => {
+                "Description of Achievement and Assessment Methods"
+            }
+            ExamRetakeNextSemester => "Exam retake next semester",
+            ExamRetakeEndSemester => "Exam retake at the end of semester",
+            Prerequisites => "Prerequisites (recommended)",
+            IntendedLearningOutcomes => "Intended Learning Outcomes",
+            Content => "Content",
+            TeachingAndLearningMethods => "Teaching and Learning Methods",
+            Media => "Media",
+            ReadingList => "Reading List",
+        })
+    }
+
+    /// Finds the field introduced by the given line of a TUMonline module page.
+    ///
+    /// When a header is shared, the first of the fields is returned (see [header](Field::header)).
+    pub fn from_header(line: &str) -> Option<Self> {
+        use Field::*;
+        Some(match line.trim() {
+            "Name" => Name,
+            "Module ID" => Id,
+            "ECTS credits" => ECTS,
+            "Version" => Version,
+            "Valid" => ValidFrom,
+            "Responsible for Module" => Responsible,
+            "Organisation" => Organisation,
+            "Note" => Note,
+            "Module Level" => ModuleLevel,
+            // TUMonline misspells this one.
+            "Abbreviation" | "Abbrevation" => Abbreviation,
+            "Subtitle" => Subtitle,
+            "Duration" => Duration,
+            "Occurrence" => Occurence,
+            "Language" => Language,
+            "Related Programs" => RelatedPrograms,
+            "Total Hours" => TotalHours,
+            "Contact Hours" => ContactHours,
+            "Self-study Hours" => SelfStudyHours,
+            "Description of Achievement and Assessment Methods" => {
+                DescrOfAchievementAssessmentMethods
+            }
+            "Exam retake next semester" => ExamRetakeNextSemester,
+            "Exam retake at the end of semester" => ExamRetakeEndSemester,
+            "Prerequisites (recommended)" => Prerequisites,
+            "Intended Learning Outcomes" => IntendedLearningOutcomes,
+            "Content" => Content,
+            "Teaching and Learning Methods" => TeachingAndLearningMethods,
+            "Media" => Media,
+            "Reading List" => ReadingList,
+            _ => return None,
+        })
+    }
+}
+
+/// A line that starts a new section of the page.
+enum Header {
+    Field(Field),
+    /// Title of a group of fields, such as "Workload". It holds no value.
+    Title,
+}
+
+fn find_header(line: &str) -> Option<Header> {
+    match line.trim() {
+        "General Data" | "Workload" | "Study and Examination Performance" | "Description"
+        | "Responsible for Module (Description)" => Some(Header::Title),
+        line => Field::from_header(line).map(Header::Field),
+    }
+}
+
+/// Reads a boolean written as yes/no or true/false.
+pub fn parse_bool(str: &str) -> Option<bool> {
+    static TRUE_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\b(?i)(y|yes|true)\b").unwrap());
+    static FALSE_REGEX: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"\b(?i)(n|no|false)\b").unwrap());
+
+    if TRUE_REGEX.is_match(str) {
+        Some(true)
+    } else if FALSE_REGEX.is_match(str) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+impl Module {
+    /// Reads a whole module page copied from TUMonline.
+    ///
+    /// Returns the module filled with the fields that were found,
+    /// and a [ParseReport] telling which fields were found, missing or could not be read.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::{Field, Module};
+    /// use std::io::Cursor;
+    ///
+    /// let page = Cursor::new(
+    /// "Module ID
+    /// MW2138
+    /// ECTS credits
+    /// 5
+    /// Valid
+    /// from winter semester 2019 until summer semester 2026
+    /// Total Hours
+    /// 150
+    /// Exam retake next semester
+    /// Yes
+    /// Content
+    /// Equations of motion,
+    /// linearisation.
+    /// Media
+    /// Slides
+    /// ");
+    ///
+    /// let (module, report) = Module::from_reader(page);
+    ///
+    /// assert_eq!(module.id, "MW2138");
+    /// assert_eq!(module.ects, 5);
+    /// assert_eq!(module.valid_until.unwrap().to_string(), "summer semester 2026");
+    /// assert_eq!(module.total_hours.whole_hours(), 150);
+    /// assert!(module.exam_retake_next_semester);
+    /// assert_eq!(module.content, "Equations of motion,\nlinearisation.");
+    /// assert_eq!(module.media, "Slides");
+    ///
+    /// assert!(report.found.contains(&Field::ValidUntil));
+    /// assert!(report.missing.contains(&Field::Name));
+    /// assert!(report.unparseable.is_empty());
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> (Self, ParseReport) {
+        let mut module = Module::default();
+        let mut report = ParseReport::default();
+
+        let lines = reader.lines().map_while(Result::ok);
+        let mut sections = get_sections(lines, &find_header);
+
+        while let Some(header) = sections.next_section() {
+            let field = match header {
+                Header::Title => continue,
+                // The second "Responsible for Module" is the one at the end of the page.
+                Header::Field(Field::Responsible) if report.found.contains(&Field::Responsible) => {
+                    Field::ResponsibleBis
+                }
+                Header::Field(field) => field,
+            };
+
+            match module.read_field(field, &mut sections) {
+                Ok(fields) => report.found.extend(fields),
+                Err(err) => report.unparseable.push((field, err)),
+            }
+        }
+
+        // The courses and exams are on their own pages.
+        report.missing = Field::ALL
+            .into_iter()
+            .filter(|field| {
+                !matches!(field, Field::Courses | Field::Exams)
+                    && !report.found.contains(field)
+                    && !report.unparseable.iter().any(|(f, _)| f == field)
+            })
+            .collect();
+
+        (module, report)
+    }
+
+    /// Reads the section of the given field and stores its value.
+    ///
+    /// Returns the fields that were set.
+    fn read_field(&mut self, field: Field, lines: &mut impl Lines) -> Result<Vec<Field>, FieldError> {
+        use Field::*;
+
+        let text = match field {
+            ValidFrom | ValidUntil => {
+                let text = lines.text();
+                let (from, _, stop) =
+                    Semester::parse_with_index(&text).map_err(FieldError::Semester)?;
+                self.valid_from = Some(from);
+                self.valid_until = Semester::parse_with_index(&text[stop..])
+                    .ok()
+                    .map(|(until, _, _)| until);
+                return Ok(if self.valid_until.is_some() {
+                    vec![ValidFrom, ValidUntil]
+                } else {
+                    vec![ValidFrom]
+                });
+            }
+            Courses | Exams => return Ok(Vec::new()),
+
+            DescrOfAchievementAssessmentMethods
+            | IntendedLearningOutcomes
+            | Content
+            | TeachingAndLearningMethods
+            | ReadingList => lines.text(),
+            _ => first_line(lines),
+        };
+
+        let value = FieldValue::parse(field, &text)?;
+        self.set(field, value)
+            .expect("a parsed value has the type of its field");
+
+        Ok(vec![field])
+    }
+}
@@ -0,0 +1,231 @@
+//! Access to the fields of a [Module] by [Field], through a single [FieldValue] type.
+
+use std::fmt::Display;
+
+use crate::displayable_option::{DisplayOption, opt_to_string};
+
+use super::*;
+
+/// The value of a field of a module, whatever its type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    #[allow(missing_docs)]
+    Text(String),
+    #[allow(missing_docs)]
+    ECTS(ECTS),
+    /// A semester that may be unknown, such as the first semester the module is valid.
+    Semester(Option<Semester>),
+    /// A duration, written in whole hours.
+    Hours(Duration),
+    #[allow(missing_docs)]
+    Bool(bool),
+    #[allow(missing_docs)]
+    Courses(Vec<Course>),
+    #[allow(missing_docs)]
+    Exams(Vec<Exam>),
+}
+
+impl FieldValue {
+    /// Reads the value of a field from the way it is [displayed](Display).
+    ///
+    /// Semesters can be `-` for an unknown semester, and booleans are `yes` or `no`.
+    /// Courses and exams can't be read from a single value.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::{Field, FieldValue, Module};
+    ///
+    /// let mut module = Module::default();
+    /// for (field, text) in [
+    ///     (Field::Name, "Flight System Dynamics"),
+    ///     (Field::ECTS, "5"),
+    ///     (Field::ValidFrom, "winter semester 2019"),
+    ///     (Field::TotalHours, "150"),
+    ///     (Field::ExamRetakeNextSemester, "yes"),
+    /// ] {
+    ///     let value = FieldValue::parse(field, text).unwrap();
+    ///     module.set(field, value).unwrap();
+    ///     assert_eq!(module.get(field).to_string(), text);
+    /// }
+    ///
+    /// assert_eq!(module.ects, 5);
+    /// assert_eq!(module.total_hours.whole_hours(), 150);
+    /// assert!(FieldValue::parse(Field::ECTS, "five").is_err());
+    /// assert_eq!(module.set(Field::ECTS, FieldValue::Bool(true)), Err(FieldValue::Bool(true)));
+    /// ```
+    pub fn parse(field: Field, str: &str) -> Result<Self, FieldError> {
+        use Field::*;
+        let str = str.trim();
+        Ok(match field {
+            ECTS => FieldValue::ECTS(str.parse().map_err(FieldError::Number)?),
+            ValidFrom | ValidUntil => FieldValue::Semester(
+                str.parse::<DisplayOption<Semester>>()
+                    .map_err(FieldError::Semester)?
+                    .into(),
+            ),
+            TotalHours | ContactHours | SelfStudyHours => FieldValue::Hours(time::Duration::hours(
+                str.parse().map_err(FieldError::Number)?,
+            )),
+            ExamRetakeNextSemester | ExamRetakeEndSemester => {
+                FieldValue::Bool(parse_bool(str).ok_or(FieldError::Bool)?)
+            }
+            Courses | Exams => return Err(FieldError::NotText),
+            Name
+            | Id
+            | Version
+            | Responsible
+            | Organisation
+            | Note
+            | ModuleLevel
+            | Abbreviation
+            | Subtitle
+            | Duration
+            | Occurence
+            | Language
+            | RelatedPrograms
+            | DescrOfAchievementAssessmentMethods
+            | Prerequisites
+            | IntendedLearningOutcomes
+            | Content
+            | TeachingAndLearningMethods
+            | Media
+            | ReadingList
+            | ResponsibleBis => FieldValue::Text(str.to_string()),
+        })
+    }
+}
+
+impl Display for FieldValue {
+    /// Courses and exams are written as their title and number, one per line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Text(text) => write!(f, "{text}"),
+            FieldValue::ECTS(ects) => write!(f, "{ects}"),
+            FieldValue::Semester(semester) => write!(f, "{}", opt_to_string(semester)),
+            FieldValue::Hours(duration) => write!(f, "{}", duration.whole_hours()),
+            FieldValue::Bool(value) => write!(f, "{}", if *value { "yes" } else { "no" }),
+            FieldValue::Courses(courses) => {
+                let lines: Vec<String> = courses
+                    .iter()
+                    .map(|course| format!("{} ({})", course.get_title(), course.get_number()))
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+            FieldValue::Exams(exams) => {
+                let lines: Vec<String> = exams
+                    .iter()
+                    .map(|exam| format!("{} ({})", exam.get_title(), exam.get_number()))
+                    .collect();
+                write!(f, "{}", lines.join("\n"))
+            }
+        }
+    }
+}
+
+impl Module {
+    /// Gets a copy of the value of a field.
+    pub fn get(&self, field: Field) -> FieldValue {
+        use Field::*;
+        let text = |text: &String| FieldValue::Text(text.clone());
+        match field {
+            Name => text(&self.name),
+            Id => text(&self.id),
+            ECTS => FieldValue::ECTS(self.ects),
+            Version => text(&self.version),
+            ValidFrom => FieldValue::Semester(self.valid_from.clone()),
+            ValidUntil => FieldValue::Semester(self.valid_until.clone()),
+            Responsible => text(&self.responsible),
+            Organisation => text(&self.organisation),
+            Note => text(&self.note),
+            Courses => FieldValue::Courses(self.courses.clone()),
+            Exams => FieldValue::Exams(self.exams.clone()),
+            ModuleLevel => text(&self.module_level),
+            Abbreviation => text(&self.abbreviation),
+            Subtitle => text(&self.subtitle),
+            Duration => text(&self.duration),
+            Occurence => text(&self.occurence),
+            Language => text(&self.language),
+            RelatedPrograms => text(&self.related_programs),
+            TotalHours => FieldValue::Hours(self.total_hours),
+            ContactHours => FieldValue::Hours(self.contact_hours),
+            SelfStudyHours => FieldValue::Hours(self.self_study_hours),
+            DescrOfAchievementAssessmentMethods => {
+                text(&self.descr_of_achievement_assessment_methods)
+            }
+            ExamRetakeNextSemester => FieldValue::Bool(self.exam_retake_next_semester),
+            ExamRetakeEndSemester => FieldValue::Bool(self.exam_retake_end_semester),
+            Prerequisites => text(&self.prerequisites),
+            IntendedLearningOutcomes => text(&self.intended_learning_outcomes),
+            Content => text(&self.content),
+            TeachingAndLearningMethods => text(&self.teaching_and_learning_methods),
+            Media => text(&self.media),
+            ReadingList => text(&self.reading_list),
+            ResponsibleBis => text(&self.responsible_bis),
+        }
+    }
+
+    /// Sets the value of a field.
+    ///
+    /// Gives the value back if it is not of the type of the field.
+    pub fn set(&mut self, field: Field, value: FieldValue) -> Result<(), FieldValue> {
+        use Field::*;
+        match (field, value) {
+            (ECTS, FieldValue::ECTS(ects)) => self.ects = ects,
+            (ValidFrom, FieldValue::Semester(semester)) => self.valid_from = semester,
+            (ValidUntil, FieldValue::Semester(semester)) => self.valid_until = semester,
+            (Courses, FieldValue::Courses(courses)) => self.courses = courses,
+            (Exams, FieldValue::Exams(exams)) => self.exams = exams,
+            (TotalHours, FieldValue::Hours(hours)) => self.total_hours = hours,
+            (ContactHours, FieldValue::Hours(hours)) => self.contact_hours = hours,
+            (SelfStudyHours, FieldValue::Hours(hours)) => self.self_study_hours = hours,
+            (ExamRetakeNextSemester, FieldValue::Bool(value)) => {
+                self.exam_retake_next_semester = value
+            }
+            (ExamRetakeEndSemester, FieldValue::Bool(value)) => {
+                self.exam_retake_end_semester = value
+            }
+            (field, FieldValue::Text(text)) => {
+                let target = match field {
+                    Name => &mut self.name,
+                    Id => &mut self.id,
+                    Version => &mut self.version,
+                    Responsible => &mut self.responsible,
+                    Organisation => &mut self.organisation,
+                    Note => &mut self.note,
+                    ModuleLevel => &mut self.module_level,
+                    Abbreviation => &mut self.abbreviation,
+                    Subtitle => &mut self.subtitle,
+                    Duration => &mut self.duration,
+                    Occurence => &mut self.occurence,
+                    Language => &mut self.language,
+                    RelatedPrograms => &mut self.related_programs,
+                    DescrOfAchievementAssessmentMethods => {
+                        &mut self.descr_of_achievement_assessment_methods
+                    }
+                    Prerequisites => &mut self.prerequisites,
+                    IntendedLearningOutcomes => &mut self.intended_learning_outcomes,
+                    Content => &mut self.content,
+                    TeachingAndLearningMethods => &mut self.teaching_and_learning_methods,
+                    Media => &mut self.media,
+                    ReadingList => &mut self.reading_list,
+                    ResponsibleBis => &mut self.responsible_bis,
+                    ECTS
+                    | ValidFrom
+                    | ValidUntil
+                    | Courses
+                    | Exams
+                    | TotalHours
+                    | ContactHours
+                    | SelfStudyHours
+                    | ExamRetakeNextSemester
+                    | ExamRetakeEndSemester => {
+                        return Err(FieldValue::Text(text));
+                    }
+                };
+                *target = text
+            }
+            (_, value) => return Err(value),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Helpers shared by the parsers of the module, course and exam pages copied from TUMonline.
+
+use std::ops::Deref;
+
+use crate::lines::Lines;
+
+use super::*;
+
+/// Returns the first non blank line of the section, trimmed.
+pub(super) fn first_line(lines: &mut impl Iterator<Item: Deref<Target = str>>) -> String {
+    lines
+        .find(|line| !line.trim().is_empty())
+        .map(|line| line.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Reads a number of hours per week, such as `2` or `1,5`.
+pub(super) fn parse_weekly_hours(str: &str) -> Option<Duration> {
+    let hours: f64 = str.trim().replace(',', ".").parse().ok()?;
+    Some(Duration::seconds_f64(hours * 3600.))
+}
+
+/// Reads a period such as `01.09.2025 - 20.10.2025` and returns its start and end.
+pub(super) fn parse_period(str: &str) -> (Option<Date>, Option<Date>) {
+    match str.split_once(" - ").or_else(|| str.split_once('-')) {
+        Some((start, end)) => (parse_date(start), parse_date(end)),
+        None => (parse_date(str), None),
+    }
+}
+
+/// A header of the registration dates, found on both course and exam pages.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum RegistrationHeader {
+    Start,
+    End,
+    DeregistrationStart,
+    DeregistrationEnd,
+    Period,
+    DeregistrationPeriod,
+}
+
+impl RegistrationHeader {
+    /// Finds the header in a line, without its trailing colon.
+    pub(super) fn find(line: &str) -> Option<Self> {
+        use RegistrationHeader::*;
+        Some(match line {
+            "Registration start" => Start,
+            "Registration end" => End,
+            "Deregistration start" => DeregistrationStart,
+            "Deregistration end" => DeregistrationEnd,
+            "Registration period" => Period,
+            "Deregistration period" => DeregistrationPeriod,
+            _ => return None,
+        })
+    }
+
+    /// Reads the dates of the section into the start and end of the registration and deregistration.
+    pub(super) fn read(
+        self,
+        lines: &mut impl Lines,
+        (registration_start, registration_end): (&mut Option<Date>, &mut Option<Date>),
+        (deregistration_start, deregistration_end): (&mut Option<Date>, &mut Option<Date>),
+    ) {
+        use RegistrationHeader::*;
+        let line = first_line(lines);
+        match self {
+            Start => *registration_start = parse_date(&line),
+            End => *registration_end = parse_date(&line),
+            DeregistrationStart => *deregistration_start = parse_date(&line),
+            DeregistrationEnd => *deregistration_end = parse_date(&line),
+            Period => (*registration_start, *registration_end) = parse_period(&line),
+            DeregistrationPeriod => {
+                (*deregistration_start, *deregistration_end) = parse_period(&line)
+            }
+        }
+    }
+}
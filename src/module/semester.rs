@@ -2,11 +2,16 @@
 
 use std::{cmp::{max, min}, fmt::Display, str::FromStr};
 
+use time::Weekday;
+
 use super::*;
 
 /// A semester. For example, 2025 summer semester.
-#[derive(Debug, Clone)]
+///
+/// Semesters are ordered chronologically: the summer semester of a year comes before its winter semester.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Semester {
+    // The order of the fields gives the chronological order.
     year: Year,
     winter: bool,
 }
@@ -39,6 +44,150 @@ impl Semester {
         &mut self.winter
     }
 
+    /// Returns the semester during which the given date falls.
+    ///
+    /// Winter semesters go from October to March, and summer semesters from April to September.
+    pub fn from_date(date: Date) -> Self {
+        match date.month() as u8 {
+            1..=3 => Self::new((date.year() - 1) as Year, true),
+            4..=9 => Self::new(date.year() as Year, false),
+            _ => Self::new(date.year() as Year, true),
+        }
+    }
+
+    /// Returns the current semester.
+    pub fn current() -> Self {
+        Self::from_date(time::OffsetDateTime::now_utc().date())
+    }
+
+    /// Returns the semester after this one.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::semester::Semester;
+    ///
+    /// assert_eq!(Semester::new(2025, false).next(), Semester::new(2025, true));
+    /// assert_eq!(Semester::new(2025, true).next(), Semester::new(2026, false));
+    /// assert_eq!(Semester::new(2026, false).prev(), Some(Semester::new(2025, true)));
+    /// assert_eq!(Semester::new(0, false).prev(), None);
+    /// ```
+    pub fn next(&self) -> Self {
+        if self.winter {
+            Self::new(self.year + 1, false)
+        } else {
+            Self::new(self.year, true)
+        }
+    }
+
+    /// Returns the semester before this one, if there is one.
+    pub fn prev(&self) -> Option<Self> {
+        if self.winter {
+            Some(Self::new(self.year, false))
+        } else {
+            Some(Self::new(self.year.checked_sub(1)?, true))
+        }
+    }
+
+    /// Number of semesters from this one to the given one. Negative if the given one is earlier.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::semester::Semester;
+    ///
+    /// let first = Semester::new(2024, true);
+    /// assert_eq!(first.semesters_until(&Semester::new(2026, false)), 3);
+    /// assert_eq!(first.semesters_until(&first), 0);
+    /// assert_eq!(first.semesters_until(&Semester::new(2024, false)), -1);
+    /// ```
+    pub fn semesters_until(&self, other: &Semester) -> i32 {
+        other.index() - self.index()
+    }
+
+    /// Position of the semester, counted in semesters since year 0.
+    fn index(&self) -> i32 {
+        self.year as i32 * 2 + self.winter as i32
+    }
+
+    /// Iterator over the semesters from this one to the given one, both included.
+    ///
+    /// It is empty if the given semester is earlier.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::semester::Semester;
+    ///
+    /// let semesters: Vec<_> = Semester::new(2025, true).until(&Semester::new(2026, true)).collect();
+    /// assert_eq!(semesters, vec![
+    ///     Semester::new(2025, true),
+    ///     Semester::new(2026, false),
+    ///     Semester::new(2026, true),
+    /// ]);
+    /// ```
+    pub fn until(&self, last: &Semester) -> SemesterRange {
+        SemesterRange {
+            next: (self <= last).then(|| self.clone()),
+            last: last.clone(),
+        }
+    }
+
+    /// First day of the semester: the 1st of April for summer semesters and the 1st of October for winter semesters.
+    pub fn start(&self) -> Date {
+        let month = if self.winter { Month::October } else { Month::April };
+        Date::from_calendar_date(self.year as i32, month, 1).expect("the 1st of a month is valid")
+    }
+
+    /// Last day of the semester: the 30th of September for summer semesters and the 31st of March for winter semesters.
+    pub fn end(&self) -> Date {
+        self.next().start().previous_day().expect("the 1st of a month is not the first date")
+    }
+
+    /// Indicates whether the date falls during the semester, see [from_date](Semester::from_date).
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::semester::Semester;
+    /// use time::{Date, Month};
+    ///
+    /// let semester = Semester::new(2025, true);
+    /// assert!(semester.contains(Date::from_calendar_date(2025, Month::October, 1).unwrap()));
+    /// assert!(semester.contains(Date::from_calendar_date(2026, Month::March, 31).unwrap()));
+    /// assert!(!semester.contains(Date::from_calendar_date(2026, Month::April, 1).unwrap()));
+    /// ```
+    pub fn contains(&self, date: Date) -> bool {
+        self.start() <= date && date <= self.end()
+    }
+
+    /// First day of the usual lecture period: the second Monday of October for winter semesters,
+    /// and the third Monday of April for summer semesters.
+    ///
+    /// The actual dates vary a bit from year to year, for example around Easter.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::module::semester::Semester;
+    /// use time::{Date, Month};
+    ///
+    /// let date = |year, month, day| Date::from_calendar_date(year, month, day).unwrap();
+    /// assert_eq!(Semester::new(2025, true).lecture_start(), date(2025, Month::October, 13));
+    /// assert_eq!(Semester::new(2025, true).lecture_end(), date(2026, Month::February, 6));
+    /// assert_eq!(Semester::new(2024, false).lecture_start(), date(2024, Month::April, 15));
+    /// assert_eq!(Semester::new(2024, false).lecture_end(), date(2024, Month::July, 19));
+    /// ```
+    pub fn lecture_start(&self) -> Date {
+        let mondays = if self.winter { 2 } else { 3 };
+        self.start()
+            .previous_day()
+            .expect("the 1st of a month is not the first date")
+            .nth_next_occurrence(Weekday::Monday, mondays)
+    }
+
+    /// Last day of the usual lecture period, a Friday: 17 weeks after its start for winter semesters,
+    /// including the Christmas break, and 14 weeks after for summer semesters.
+    pub fn lecture_end(&self) -> Date {
+        let weeks = if self.winter { 17 } else { 14 };
+        self.lecture_start() + Duration::weeks(weeks) - Duration::days(3)
+    }
+
     /// Reads the string and finds a semester description in it.
     /// 
     /// Returns the semester and the starting (inclusive) and ending (exclusive) indexes of the match.
@@ -47,8 +196,8 @@ impl Semester {
 
         let year = str[year_match.range()].parse().map_err(|_| ParseError::CouldNotFindYear)?;
 
-        let winter_match = WINTER_REGEX.find(str);
-        let summer_match = SUMMER_REGEX.find(str);
+        let winter_match = WINTER_REGEX.captures(str).and_then(|c| c.get(1));
+        let summer_match = SUMMER_REGEX.captures(str).and_then(|c| c.get(1));
 
         let (is_winter, season_match) = match (winter_match, summer_match) {
             (None, None) => return Err(ParseError::CouldNotFindSeason),
@@ -71,6 +220,25 @@ impl Semester {
     }
 }
 
+/// Iterator over consecutive semesters, returned by [Semester::until].
+#[derive(Debug, Clone)]
+pub struct SemesterRange {
+    next: Option<Semester>,
+    last: Semester,
+}
+
+impl Iterator for SemesterRange {
+    type Item = Semester;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let semester = self.next.take()?;
+        if semester < self.last {
+            self.next = Some(semester.next())
+        }
+        Some(semester)
+    }
+}
+
 impl Display for Semester {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -83,8 +251,9 @@ impl Display for Semester {
 }
 
 static YEAR_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
-static SUMMER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\b{start}(?:S|Summer)\b{end}").unwrap());
-static WINTER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\b{start}(?:W|Winter)\b{end}").unwrap());
+// The season may be glued to the year, as in `W2025`, so digits count as word boundaries too.
+static SUMMER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(?:\A|[^a-z])(S|Summer)(?:[^a-z]|\z)").unwrap());
+static WINTER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(?:\A|[^a-z])(W|Winter)(?:[^a-z]|\z)").unwrap());
 
 /// Error returned when [FromStr] fails on a [Semester].
 #[derive(Debug, Clone, Copy)]
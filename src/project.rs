@@ -0,0 +1,141 @@
+//! Project files, used to save a module tree on disk and open it again.
+//!
+//! A project is written as [RON](ron) so that it stays readable and can be edited by hand if needed.
+
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    academic_calendar::AcademicCalendar,
+    module::Module,
+    schedule::GroupSelection,
+    storage_tree::StorageTree,
+    study_plan::StudyPlan,
+};
+
+/// The extension used for project files.
+pub const EXTENSION: &str = "ron";
+
+/// Version of the file format written by [to_writer](Project::to_writer).
+const FORMAT_VERSION: u32 = 1;
+
+/// Everything that is saved in a project file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    version: u32,
+    /// The module tree.
+    pub tree: StorageTree<String, Module>,
+    /// Semester in which each module is planned.
+    #[serde(default)]
+    pub planned: StudyPlan,
+    /// The group chosen for the courses of the modules.
+    #[serde(default)]
+    pub groups: GroupSelection,
+    /// The academic calendar used to plan the semesters.
+    #[serde(default)]
+    pub calendar: AcademicCalendar,
+}
+
+/// Error returned when a [Project] can't be read or written.
+#[derive(Debug)]
+pub enum Error {
+    /// The file could not be opened, read or written.
+    Io(io::Error),
+    /// The content of the file is not a valid project.
+    Parse(ron::error::SpannedError),
+    /// The project could not be turned into text.
+    Serialize(ron::Error),
+    /// The file was written by a newer version of the app.
+    UnsupportedVersion(u32),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "Can't access the file: {err}"),
+            Error::Parse(err) => write!(f, "The file is not a valid project: {err}"),
+            Error::Serialize(err) => write!(f, "Can't write the project: {err}"),
+            Error::UnsupportedVersion(version) => write!(
+                f,
+                "The file uses format version {version}, but only versions up to {FORMAT_VERSION} are supported"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl Project {
+    /// Creates a new [Project] holding the given tree.
+    pub fn new(tree: StorageTree<String, Module>) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            tree,
+            planned: StudyPlan::new(),
+            groups: GroupSelection::new(),
+            calendar: AcademicCalendar::default(),
+        }
+    }
+
+    /// Reads a project.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::{module::{Module, semester::Semester}, project::Project, storage_tree::StorageTree};
+    ///
+    /// let mut module = Module::default();
+    /// module.name = "Flight System Dynamics".to_string();
+    /// module.ects = 5;
+    /// module.valid_from = Some(Semester::new(2025, true));
+    ///
+    /// let tree = StorageTree::node("Aerospace".to_string(), vec![StorageTree::leaf(module)]);
+    ///
+    /// let mut file = Vec::new();
+    /// Project::new(tree).to_writer(&mut file).unwrap();
+    ///
+    /// let project = Project::from_reader(file.as_slice()).unwrap();
+    /// let module = project.tree.leaf_iter().next().unwrap();
+    /// assert_eq!(module.name, "Flight System Dynamics");
+    /// assert_eq!(module.ects, 5);
+    /// assert_eq!(module.valid_from.as_ref().unwrap().to_string(), "winter semester 2025");
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        let mut project: Self = ron::de::from_reader(reader).map_err(Error::Parse)?;
+        if project.version > FORMAT_VERSION {
+            return Err(Error::UnsupportedVersion(project.version));
+        }
+        // Older files are upgraded when they are saved again.
+        project.version = FORMAT_VERSION;
+        Ok(project)
+    }
+
+    /// Writes the project.
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), Error> {
+        ron::ser::to_writer_pretty(writer, self, ron::ser::PrettyConfig::default())
+            .map_err(Error::Serialize)
+    }
+
+    /// Opens the project file at the given path.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Saves the project in the file at the given path, replacing its content.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.to_writer(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+//! Degree requirements, as written in the examination regulations (FPSO),
+//! and their evaluation against the modules taken.
+
+use std::fmt::Display;
+
+use crate::{
+    module::{ECTS, Module},
+    storage_tree::StorageTree,
+};
+
+mod programme;
+pub use programme::*;
+
+/// The modules a rule is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// Every module of the tree.
+    All,
+    /// The modules inside the folders with this name, at any depth. For example a catalogue.
+    Folder(String),
+    /// The modules with these ids.
+    Modules(Vec<String>),
+    /// The modules chosen by any of these selectors.
+    Any(Vec<Selector>),
+}
+
+/// What a [Rule] requires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Requirement {
+    /// At least this many ECTS credits from the selected modules.
+    MinEcts(Selector, ECTS),
+    /// At most this many ECTS credits from the selected modules.
+    MaxEcts(Selector, ECTS),
+    /// The module with this id must be taken.
+    Mandatory(String),
+    /// At most one of the modules with these ids may be taken.
+    Exclusive(Vec<String>),
+}
+
+/// A degree requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// Name shown to the user, such as "Required electives".
+    pub name: String,
+    #[allow(missing_docs)]
+    pub requirement: Requirement,
+}
+
+/// The modules taken, given by their id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Standing {
+    /// The modules passed.
+    pub completed: Vec<String>,
+    /// The modules that will be taken.
+    pub planned: Vec<String>,
+}
+
+/// Result of a [Rule].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The rule is fulfilled, counting the planned modules.
+    Satisfied,
+    /// This many ECTS credits are still needed.
+    ///
+    /// For a [mandatory](Requirement::Mandatory) module, these are its credits,
+    /// or 0 if it is not in the tree.
+    Missing(ECTS),
+    /// This many ECTS credits are too many.
+    ///
+    /// For [mutually exclusive](Requirement::Exclusive) modules, these are the credits
+    /// of the modules taken after the first one.
+    Violated(ECTS),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Satisfied => write!(f, "Satisfied"),
+            Status::Missing(ects) => write!(f, "{ects} ECTS missing"),
+            Status::Violated(ects) => write!(f, "{ects} ECTS too many"),
+        }
+    }
+}
+
+/// A module counted for a rule.
+#[derive(Debug, Clone, Copy)]
+pub struct Contribution<'a> {
+    #[allow(missing_docs)]
+    pub module: &'a Module,
+    /// Indicates whether the module is only planned, and not completed yet.
+    pub planned: bool,
+}
+
+/// Result of [evaluate] for one rule.
+#[derive(Debug, Clone)]
+pub struct Report<'a> {
+    #[allow(missing_docs)]
+    pub rule: &'a Rule,
+    #[allow(missing_docs)]
+    pub status: Status,
+    /// The modules taken that count for the rule.
+    pub contributions: Vec<Contribution<'a>>,
+}
+
+impl Report<'_> {
+    /// Sums up the ECTS credits of the completed modules counted for the rule.
+    pub fn completed_ects(&self) -> ECTS {
+        self.contributions
+            .iter()
+            .filter(|contribution| !contribution.planned)
+            .map(|contribution| contribution.module.ects)
+            .sum()
+    }
+
+    /// Sums up the ECTS credits of the planned modules counted for the rule.
+    pub fn planned_ects(&self) -> ECTS {
+        self.contributions
+            .iter()
+            .filter(|contribution| contribution.planned)
+            .map(|contribution| contribution.module.ects)
+            .sum()
+    }
+}
+
+impl Selector {
+    /// The modules of the tree chosen by the selector, each id only once.
+    pub fn select<'a>(&self, tree: &'a StorageTree<String, Module>) -> Vec<&'a Module> {
+        let mut modules = Vec::new();
+        collect(self, tree, false, &mut modules);
+        let mut seen = Vec::new();
+        modules.retain(|module| {
+            let new = !seen.contains(&&module.id);
+            seen.push(&module.id);
+            new
+        });
+        modules
+    }
+}
+
+/// Adds the modules of the tree chosen by the selector to `modules`.
+///
+/// `inside` tells whether the tree is in a selected folder.
+fn collect<'a>(
+    selector: &Selector,
+    tree: &'a StorageTree<String, Module>,
+    inside: bool,
+    modules: &mut Vec<&'a Module>,
+) {
+    if let Selector::Any(selectors) = selector {
+        for selector in selectors {
+            collect(selector, tree, inside, modules)
+        }
+        return;
+    }
+
+    match tree {
+        StorageTree::Leaf(module) => {
+            let selected = match selector {
+                Selector::All => true,
+                Selector::Folder(_) => inside,
+                Selector::Modules(ids) => ids.contains(&module.id),
+                Selector::Any(_) => unreachable!("handled above"),
+            };
+            if selected {
+                modules.push(module)
+            }
+        }
+        StorageTree::Node(node) => {
+            let inside =
+                inside || matches!(selector, Selector::Folder(name) if name == node.get_key());
+            for child in node.get_children() {
+                collect(selector, child, inside, modules)
+            }
+        }
+    }
+}
+
+/// Evaluates each rule against the modules of the tree that are completed or planned.
+///
+/// A module that is both completed and planned counts as completed.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::{
+///     module::Module,
+///     requirements::*,
+///     storage_tree::StorageTree,
+/// };
+///
+/// let module = |id: &str, ects| {
+///     let mut module = Module::default();
+///     module.id = id.to_string();
+///     module.ects = ects;
+///     StorageTree::leaf(module)
+/// };
+/// let tree = StorageTree::node("Master".to_string(), vec![
+///     StorageTree::node("Required electives".to_string(), vec![
+///         module("MW2138", 5),
+///         module("MW0537", 6),
+///         module("MW1903", 5),
+///     ]),
+///     StorageTree::node("Soft skills".to_string(), vec![
+///         module("ED0001", 6),
+///         module("ED0002", 6),
+///     ]),
+/// ]);
+///
+/// let rules = [
+///     Rule {
+///         name: "Required electives".to_string(),
+///         requirement: Requirement::MinEcts(Selector::Folder("Required electives".to_string()), 15),
+///     },
+///     Rule {
+///         name: "Soft skills".to_string(),
+///         requirement: Requirement::MaxEcts(Selector::Folder("Soft skills".to_string()), 10),
+///     },
+///     Rule {
+///         name: "Flight System Dynamics".to_string(),
+///         requirement: Requirement::Mandatory("MW2138".to_string()),
+///     },
+/// ];
+/// let standing = Standing {
+///     completed: vec!["MW2138".to_string(), "ED0001".to_string()],
+///     planned: vec!["MW0537".to_string(), "ED0002".to_string()],
+/// };
+///
+/// let reports = evaluate(&rules, &tree, &standing);
+///
+/// assert_eq!(reports[0].status, Status::Missing(4));
+/// assert_eq!(reports[0].completed_ects(), 5);
+/// assert_eq!(reports[0].planned_ects(), 6);
+/// assert_eq!(reports[1].status, Status::Violated(2));
+/// assert_eq!(reports[2].status, Status::Satisfied);
+/// assert_eq!(reports[2].contributions[0].module.id, "MW2138");
+/// ```
+pub fn evaluate<'a>(
+    rules: &'a [Rule],
+    tree: &'a StorageTree<String, Module>,
+    standing: &Standing,
+) -> Vec<Report<'a>> {
+    let contribution = |module: &'a Module| {
+        if standing.completed.contains(&module.id) {
+            Some(Contribution {
+                module,
+                planned: false,
+            })
+        } else if standing.planned.contains(&module.id) {
+            Some(Contribution {
+                module,
+                planned: true,
+            })
+        } else {
+            None
+        }
+    };
+
+    rules
+        .iter()
+        .map(|rule| {
+            let (status, contributions) = match &rule.requirement {
+                Requirement::MinEcts(selector, min) => {
+                    let contributions: Vec<_> = selector
+                        .select(tree)
+                        .into_iter()
+                        .filter_map(contribution)
+                        .collect();
+                    let total = sum(&contributions);
+                    let status = if total >= *min {
+                        Status::Satisfied
+                    } else {
+                        Status::Missing(min - total)
+                    };
+                    (status, contributions)
+                }
+                Requirement::MaxEcts(selector, max) => {
+                    let contributions: Vec<_> = selector
+                        .select(tree)
+                        .into_iter()
+                        .filter_map(contribution)
+                        .collect();
+                    let total = sum(&contributions);
+                    let status = if total <= *max {
+                        Status::Satisfied
+                    } else {
+                        Status::Violated(total - max)
+                    };
+                    (status, contributions)
+                }
+                Requirement::Mandatory(id) => {
+                    let module = tree.leaf_iter().find(|module| module.id == *id);
+                    match module.and_then(contribution) {
+                        Some(contribution) => (Status::Satisfied, vec![contribution]),
+                        None => (
+                            Status::Missing(module.map(|module| module.ects).unwrap_or_default()),
+                            Vec::new(),
+                        ),
+                    }
+                }
+                Requirement::Exclusive(ids) => {
+                    let contributions: Vec<_> = Selector::Modules(ids.clone())
+                        .select(tree)
+                        .into_iter()
+                        .filter_map(contribution)
+                        .collect();
+                    let status = match contributions.split_first() {
+                        Some((_, others)) if !others.is_empty() => Status::Violated(sum(others)),
+                        _ => Status::Satisfied,
+                    };
+                    (status, contributions)
+                }
+            };
+            Report {
+                rule,
+                status,
+                contributions,
+            }
+        })
+        .collect()
+}
+
+/// Sums up the ECTS credits of the contributions.
+fn sum(contributions: &[Contribution]) -> ECTS {
+    contributions
+        .iter()
+        .map(|contribution| contribution.module.ects)
+        .sum()
+}
@@ -0,0 +1,267 @@
+//! Study programme files, describing the requirement categories of a degree.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::File,
+    io::{self, BufReader, Read},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// The extension used for study programme files.
+pub const EXTENSION: &str = "ron";
+
+/// What counts toward a [Category].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Counts {
+    /// The modules inside the folders of the module tree with this name.
+    Folder(String),
+    /// The modules listed under this tag in the [tags](Programme::tags) of the programme.
+    Tag(String),
+    /// The module with this id.
+    Module(String),
+}
+
+/// A requirement category of a study programme, such as "Required electives".
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Category {
+    #[allow(missing_docs)]
+    pub name: String,
+    /// Minimum number of ECTS credits from the modules that count toward the category.
+    pub min_ects: Option<ECTS>,
+    /// Maximum number of ECTS credits from the modules that count toward the category.
+    pub max_ects: Option<ECTS>,
+    /// Ids of the modules that must be taken.
+    pub mandatory: Vec<String>,
+    /// Sets of module ids among which at most one module may be taken.
+    pub exclusive: Vec<Vec<String>>,
+    /// What counts toward the category.
+    pub counts: Vec<Counts>,
+}
+
+/// A study programme and its requirements, as written in its examination regulations (FPSO).
+///
+/// Programmes are written as [RON](ron) so that they can be written by hand and shared.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::requirements::Programme;
+///
+/// let programme = Programme::from_reader(r#"
+/// Programme(
+///     name: "M.Sc. Aerospace",
+///     tags: {
+///         "Flight": ["MW2138", "MW0537"],
+///     },
+///     categories: [
+///         Category(
+///             name: "Required electives",
+///             min_ects: Some(30),
+///             mandatory: ["MW2138"],
+///             exclusive: [["MW0537", "MW0538"]],
+///             counts: [Folder("Required electives"), Tag("Flight")],
+///         ),
+///         Category(
+///             name: "Soft skills",
+///             max_ects: Some(10),
+///             counts: [Folder("Soft skills")],
+///         ),
+///     ],
+/// )
+/// "#.as_bytes()).unwrap();
+///
+/// assert_eq!(programme.categories.len(), 2);
+/// assert_eq!(programme.rules().len(), 4);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Programme {
+    /// Name of the study programme.
+    pub name: String,
+    /// Named lists of module ids, that categories can count with [Counts::Tag].
+    pub tags: BTreeMap<String, Vec<String>>,
+    #[allow(missing_docs)]
+    pub categories: Vec<Category>,
+}
+
+/// Error returned when a [Programme] can't be read.
+#[derive(Debug)]
+pub enum ProgrammeError {
+    /// The file could not be opened or read.
+    Io(io::Error),
+    /// The content of the file is not a valid programme.
+    Parse(ron::error::SpannedError),
+    /// Two categories have the same name.
+    DuplicateCategory(String),
+    /// A category counts a tag that is not defined.
+    UnknownTag {
+        #[allow(missing_docs)]
+        category: String,
+        #[allow(missing_docs)]
+        tag: String,
+    },
+    /// The minimum of a category is above its maximum.
+    MinAboveMax {
+        #[allow(missing_docs)]
+        category: String,
+        #[allow(missing_docs)]
+        min: ECTS,
+        #[allow(missing_docs)]
+        max: ECTS,
+    },
+    /// A category has a credit limit, but nothing counts toward it.
+    NothingCounts(String),
+}
+
+impl Display for ProgrammeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProgrammeError::Io(err) => write!(f, "Can't access the file: {err}"),
+            ProgrammeError::Parse(err) => write!(
+                f,
+                "The file is not a valid programme, at line {}, column {}: {}",
+                err.position.line, err.position.col, err.code
+            ),
+            ProgrammeError::DuplicateCategory(category) => {
+                write!(f, "The category '{category}' is defined twice")
+            }
+            ProgrammeError::UnknownTag { category, tag } => write!(
+                f,
+                "The category '{category}' counts the tag '{tag}', which is not in the tags of the programme"
+            ),
+            ProgrammeError::MinAboveMax { category, min, max } => write!(
+                f,
+                "The category '{category}' requires at least {min} ECTS but allows at most {max} ECTS"
+            ),
+            ProgrammeError::NothingCounts(category) => write!(
+                f,
+                "The category '{category}' has an ECTS limit, but nothing counts toward it. Add a folder, tag or module to its counts"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProgrammeError {}
+
+impl From<io::Error> for ProgrammeError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl Programme {
+    /// Reads a programme and checks that it is consistent.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::requirements::Programme;
+    ///
+    /// let err = Programme::from_reader(r#"
+    /// Programme(
+    ///     categories: [
+    ///         Category(name: "Electives", min_ects: Some(30), counts: [Tag("Flight")]),
+    ///     ],
+    /// )
+    /// "#.as_bytes()).unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "The category 'Electives' counts the tag 'Flight', which is not in the tags of the programme"
+    /// );
+    ///
+    /// let err = Programme::from_reader("Programme(categorys: [])".as_bytes()).unwrap_err();
+    /// assert!(err.to_string().starts_with("The file is not a valid programme, at line 1, column"));
+    /// ```
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, ProgrammeError> {
+        let programme: Self = ron::de::from_reader(reader).map_err(ProgrammeError::Parse)?;
+        programme.validate()?;
+        Ok(programme)
+    }
+
+    /// Opens the programme file at the given path.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ProgrammeError> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Checks that the categories have different names, only count defined tags
+    /// and have coherent credit limits.
+    fn validate(&self) -> Result<(), ProgrammeError> {
+        for (index, category) in self.categories.iter().enumerate() {
+            if self.categories[..index]
+                .iter()
+                .any(|other| other.name == category.name)
+            {
+                return Err(ProgrammeError::DuplicateCategory(category.name.clone()));
+            }
+            for counts in &category.counts {
+                if let Counts::Tag(tag) = counts
+                    && !self.tags.contains_key(tag)
+                {
+                    return Err(ProgrammeError::UnknownTag {
+                        category: category.name.clone(),
+                        tag: tag.clone(),
+                    });
+                }
+            }
+            if let (Some(min), Some(max)) = (category.min_ects, category.max_ects)
+                && min > max
+            {
+                return Err(ProgrammeError::MinAboveMax {
+                    category: category.name.clone(),
+                    min,
+                    max,
+                });
+            }
+            if (category.min_ects.is_some() || category.max_ects.is_some())
+                && category.counts.is_empty()
+            {
+                return Err(ProgrammeError::NothingCounts(category.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The modules counting toward a category.
+    fn selector(&self, category: &Category) -> Selector {
+        Selector::Any(
+            category
+                .counts
+                .iter()
+                .map(|counts| match counts {
+                    Counts::Folder(folder) => Selector::Folder(folder.clone()),
+                    Counts::Tag(tag) => {
+                        Selector::Modules(self.tags.get(tag).cloned().unwrap_or_default())
+                    }
+                    Counts::Module(id) => Selector::Modules(vec![id.clone()]),
+                })
+                .collect(),
+        )
+    }
+
+    /// The rules to [evaluate] for the programme, named after their category.
+    pub fn rules(&self) -> Vec<Rule> {
+        let mut rules = Vec::new();
+        for category in &self.categories {
+            let rule = |requirement| Rule {
+                name: category.name.clone(),
+                requirement,
+            };
+            if let Some(min) = category.min_ects {
+                rules.push(rule(Requirement::MinEcts(self.selector(category), min)))
+            }
+            if let Some(max) = category.max_ects {
+                rules.push(rule(Requirement::MaxEcts(self.selector(category), max)))
+            }
+            for id in &category.mandatory {
+                rules.push(rule(Requirement::Mandatory(id.clone())))
+            }
+            for ids in &category.exclusive {
+                rules.push(rule(Requirement::Exclusive(ids.clone())))
+            }
+        }
+        rules
+    }
+}
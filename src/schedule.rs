@@ -0,0 +1,394 @@
+//! Choice of the groups of the courses, and a solver finding the combinations without conflicts.
+
+use std::{collections::BTreeMap, collections::BTreeSet, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+use time::{Date, Time};
+
+use crate::module::{Module, appointment::Appointment, course::Course};
+
+/// Appointments starting before this time are early mornings.
+pub const EARLY_MORNING: Time = match Time::from_hms(9, 0, 0) {
+    Ok(time) => time,
+    Err(_) => panic!("09:00 is a valid time"),
+};
+
+/// Number of partial combinations that [solve] tries at most, so that plans with many courses with
+/// several groups are not searched for too long.
+pub const MAX_STEPS: usize = 500_000;
+
+/// Returns the key identifying a course in a module: its number, or its title if it has none.
+pub fn course_key(course: &Course) -> &String {
+    if course.get_number().is_empty() {
+        course.get_title()
+    } else {
+        course.get_number()
+    }
+}
+
+/// The group chosen for the courses of the modules.
+///
+/// Courses are identified by the id of their module and their [course_key].
+/// Every group of a course without choice is considered chosen.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GroupSelection {
+    groups: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl GroupSelection {
+    /// Creates an empty selection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the group chosen for a course, if any.
+    pub fn get(&self, module: &Module, course: &Course) -> Option<&String> {
+        self.groups.get(&module.id)?.get(course_key(course))
+    }
+
+    /// Chooses a group for a course, replacing the previous choice.
+    pub fn select(&mut self, module: &Module, course: &Course, group: String) {
+        self.groups
+            .entry(module.id.clone())
+            .or_default()
+            .insert(course_key(course).clone(), group);
+    }
+
+    /// Removes the choice for a course, so that all its groups are considered chosen again.
+    pub fn unselect(&mut self, module: &Module, course: &Course) {
+        if let Some(courses) = self.groups.get_mut(&module.id) {
+            courses.remove(course_key(course));
+            if courses.is_empty() {
+                self.groups.remove(&module.id);
+            }
+        }
+    }
+
+    /// Indicates whether the group of a course is chosen.
+    pub fn is_chosen(&self, module: &Module, course: &Course, group: &str) -> bool {
+        self.get(module, course)
+            .is_none_or(|chosen| chosen == group)
+    }
+
+    /// Iterator over the choices, as module id, [course_key] and group.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String, &String)> {
+        self.groups.iter().flat_map(|(module, courses)| {
+            courses
+                .iter()
+                .map(move |(course, group)| (module, course, group))
+        })
+    }
+
+    /// Adds all the choices of another selection, replacing the ones for the same courses.
+    pub fn extend(&mut self, other: GroupSelection) {
+        for (module, courses) in other.groups {
+            self.groups.entry(module).or_default().extend(courses);
+        }
+    }
+}
+
+/// What the [solve] function tries to reduce first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Preference {
+    /// Fewest days with an appointment starting before [EARLY_MORNING].
+    #[default]
+    FewestEarlyMornings,
+    /// Fewest days with an appointment.
+    FewestCampusDays,
+}
+
+impl Preference {
+    /// All the preferences.
+    pub const ALL: [Preference; 2] = [
+        Preference::FewestEarlyMornings,
+        Preference::FewestCampusDays,
+    ];
+}
+
+impl Display for Preference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Preference::FewestEarlyMornings => write!(f, "Fewest early mornings"),
+            Preference::FewestCampusDays => write!(f, "Fewest campus days"),
+        }
+    }
+}
+
+/// What a combination of groups costs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cost {
+    /// Number of days with an appointment starting before [EARLY_MORNING].
+    pub early_mornings: usize,
+    /// Number of days with an appointment.
+    pub campus_days: usize,
+}
+
+impl Cost {
+    /// Computes the cost of a set of appointments.
+    pub fn of<'a>(appointments: impl IntoIterator<Item = &'a Appointment>) -> Self {
+        let mut days = BTreeSet::<Date>::new();
+        let mut early = BTreeSet::<Date>::new();
+        for appointment in appointments {
+            let start = appointment.get_start();
+            days.insert(start.date());
+            if start.time() < EARLY_MORNING {
+                early.insert(start.date());
+            }
+        }
+        Self {
+            early_mornings: early.len(),
+            campus_days: days.len(),
+        }
+    }
+
+    /// The key used to rank costs, lowest first.
+    fn rank(&self, preference: Preference) -> (usize, usize) {
+        match preference {
+            Preference::FewestEarlyMornings => (self.early_mornings, self.campus_days),
+            Preference::FewestCampusDays => (self.campus_days, self.early_mornings),
+        }
+    }
+}
+
+impl Display for Cost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} early mornings, {} campus days",
+            self.early_mornings, self.campus_days
+        )
+    }
+}
+
+/// A combination of groups without conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    /// The chosen group of every course that has several groups.
+    pub groups: GroupSelection,
+    /// The cost of the combination.
+    pub cost: Cost,
+}
+
+/// A course with several groups, among which one must be chosen.
+struct Choice<'a> {
+    module: &'a Module,
+    course: &'a Course,
+    groups: Vec<(&'a String, &'a Vec<Appointment>)>,
+}
+
+/// Searches the combinations of groups of the courses of the modules without conflicts,
+/// and returns the `limit` best ones, best first.
+///
+/// The courses with a single group and the exams are always part of the combination.
+/// Conflicts between them can't be avoided by choosing groups, so they are ignored.
+///
+/// The search stops after [MAX_STEPS] steps, with the best combinations found so far.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::{module::{Module, course::Course}, schedule::*};
+/// use std::io::Cursor;
+///
+/// let mut module = Module::default();
+/// module.id = "MW2138".to_string();
+/// module.courses.push(Course::from_reader(Cursor::new(
+/// "Title
+/// Lecture
+/// Dates and groups
+/// 15Oct 2025
+/// Wednesday, 10:00 - 12:00
+/// MW 2050
+/// ")));
+/// module.courses.push(Course::from_reader(Cursor::new(
+/// "Title
+/// Tutorial
+/// Dates and groups
+/// Group 1
+/// 15Oct 2025
+/// Wednesday, 11:00 - 12:00
+/// MW 1801
+/// Group 2
+/// 16Oct 2025
+/// Thursday, 08:00 - 09:00
+/// MW 1801
+/// Group 3
+/// 15Oct 2025
+/// Wednesday, 14:00 - 15:00
+/// MW 1801
+/// ")));
+///
+/// let solutions = solve([&module], Preference::FewestEarlyMornings, 5);
+///
+/// // Group 1 clashes with the lecture.
+/// assert_eq!(solutions.len(), 2);
+/// let tutorial = &module.courses[1];
+/// assert_eq!(solutions[0].groups.get(&module, tutorial).unwrap(), "Group 3");
+/// assert_eq!(solutions[0].cost.campus_days, 1);
+/// assert_eq!(solutions[1].groups.get(&module, tutorial).unwrap(), "Group 2");
+/// assert_eq!(solutions[1].cost.early_mornings, 1);
+///
+/// assert_eq!(solve([&module], Preference::FewestEarlyMornings, 1).len(), 1);
+/// ```
+pub fn solve<'a>(
+    modules: impl IntoIterator<Item = &'a Module>,
+    preference: Preference,
+    limit: usize,
+) -> Vec<Solution> {
+    let mut search = Search {
+        choices: Vec::new(),
+        preference,
+        limit,
+        appointments: Vec::new(),
+        days: BTreeMap::new(),
+        early: BTreeMap::new(),
+        chosen: Vec::new(),
+        best: Vec::new(),
+        steps: 0,
+    };
+
+    for module in modules {
+        for course in &module.courses {
+            let mut groups: Vec<_> = course.get_dates().iter().collect();
+            groups.sort_by_key(|(group, _)| *group);
+            if groups.len() > 1 {
+                search.choices.push(Choice {
+                    module,
+                    course,
+                    groups,
+                })
+            } else {
+                for (_, dates) in groups {
+                    search.add(dates);
+                }
+            }
+        }
+        for exam in &module.exams {
+            search.add(exam.get_dates());
+        }
+    }
+
+    if limit > 0 {
+        search.search();
+    }
+
+    search
+        .best
+        .into_iter()
+        .map(|(cost, chosen)| {
+            let mut groups = GroupSelection::new();
+            for (choice, group) in search.choices.iter().zip(chosen) {
+                groups.select(choice.module, choice.course, group.clone());
+            }
+            Solution { groups, cost }
+        })
+        .collect()
+}
+
+/// State of the branch and bound search of [solve].
+struct Search<'a> {
+    choices: Vec<Choice<'a>>,
+    preference: Preference,
+    limit: usize,
+    /// The appointments of the fixed courses and exams, and of the groups chosen so far.
+    appointments: Vec<&'a Appointment>,
+    /// Number of the appointments on each day.
+    days: BTreeMap<Date, usize>,
+    /// Number of the appointments starting before [EARLY_MORNING] on each day.
+    early: BTreeMap<Date, usize>,
+    /// The groups chosen so far, one per choice.
+    chosen: Vec<&'a String>,
+    /// The best combinations found so far, best first.
+    best: Vec<(Cost, Vec<&'a String>)>,
+    /// Number of partial combinations tried so far.
+    steps: usize,
+}
+
+impl<'a> Search<'a> {
+    fn add(&mut self, dates: &'a [Appointment]) {
+        for appointment in dates {
+            let start = appointment.get_start();
+            *self.days.entry(start.date()).or_default() += 1;
+            if start.time() < EARLY_MORNING {
+                *self.early.entry(start.date()).or_default() += 1;
+            }
+            self.appointments.push(appointment);
+        }
+    }
+
+    fn remove(&mut self, dates: &'a [Appointment]) {
+        let decrement = |counts: &mut BTreeMap<Date, usize>, date: Date| {
+            if let Some(count) = counts.get_mut(&date) {
+                *count -= 1;
+                if *count == 0 {
+                    counts.remove(&date);
+                }
+            }
+        };
+        for appointment in dates {
+            let start = appointment.get_start();
+            decrement(&mut self.days, start.date());
+            if start.time() < EARLY_MORNING {
+                decrement(&mut self.early, start.date());
+            }
+        }
+        self.appointments
+            .truncate(self.appointments.len() - dates.len());
+    }
+
+    /// The cost of the appointments so far, which can only grow as groups are chosen.
+    fn cost(&self) -> Cost {
+        Cost {
+            early_mornings: self.early.len(),
+            campus_days: self.days.len(),
+        }
+    }
+
+    /// Indicates whether a combination of this cost would be kept among the best ones.
+    fn is_promising(&self, cost: &Cost) -> bool {
+        self.best.len() < self.limit
+            || self
+                .best
+                .last()
+                .is_some_and(|(worst, _)| cost.rank(self.preference) < worst.rank(self.preference))
+    }
+
+    /// Chooses a group for the next choice, then for the following ones recursively,
+    /// leaving out the groups that clash with the appointments so far.
+    fn search(&mut self) {
+        let cost = self.cost();
+        if self.steps >= MAX_STEPS || !self.is_promising(&cost) {
+            return;
+        }
+        self.steps += 1;
+        let depth = self.chosen.len();
+        if depth == self.choices.len() {
+            let rank = cost.rank(self.preference);
+            let index = self
+                .best
+                .partition_point(|(other, _)| other.rank(self.preference) <= rank);
+            self.best.insert(index, (cost, self.chosen.clone()));
+            self.best.truncate(self.limit);
+            return;
+        }
+
+        for index in 0..self.choices[depth].groups.len() {
+            let (group, dates) = self.choices[depth].groups[index];
+            // The groups chosen before were checked already, only the new one can clash.
+            let conflict = dates.iter().any(|appointment| {
+                self.appointments
+                    .iter()
+                    .any(|other| appointment.overlap(other).is_some())
+            });
+            if conflict {
+                continue;
+            }
+
+            self.add(dates);
+            self.chosen.push(group);
+            self.search();
+            self.chosen.pop();
+            self.remove(dates);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Full-text search in the modules of a tree.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+use crate::{
+    module::{Field, FieldValue, Module},
+    storage_tree::{Path, StorageTree},
+};
+
+mod index;
+pub use index::*;
+
+/// Where a query was found in a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The field containing the query.
+    pub field: Field,
+    /// The text of the field, see [text].
+    pub text: String,
+    /// Position of the first word of the text matching a word of the query, in bytes.
+    pub range: Range<usize>,
+}
+
+/// The text of a field that can be searched, if any.
+///
+/// Courses and exams are searched by title and number, one per line.
+/// Numbers, semesters, durations and booleans are not searched.
+pub fn text(module: &Module, field: Field) -> Option<String> {
+    match module.get(field) {
+        FieldValue::Text(text) => Some(text),
+        value @ (FieldValue::Courses(_) | FieldValue::Exams(_)) => Some(value.to_string()),
+        FieldValue::ECTS(_)
+        | FieldValue::Semester(_)
+        | FieldValue::Hours(_)
+        | FieldValue::Bool(_) => None,
+    }
+}
+
+/// The modules of a tree matching a query.
+#[derive(Debug, Clone, Default)]
+pub struct Results {
+    /// The matches of each matching module, by path.
+    matches: BTreeMap<Path, Vec<Match>>,
+    /// The paths of the matching modules and of their ancestors.
+    visible: BTreeSet<Path>,
+    /// The paths of the matching modules, the best match first.
+    ranking: Vec<Path>,
+}
+
+impl Results {
+    /// Searches the query in every module of the tree, see [Index::search].
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::{module::{Field, Module}, search::Results, storage_tree::StorageTree};
+    ///
+    /// let module = |name: &str, content: &str| {
+    ///     let mut module = Module::default();
+    ///     module.name = name.to_string();
+    ///     module.content = content.to_string();
+    ///     StorageTree::leaf(module)
+    /// };
+    /// let tree = StorageTree::node("Master", vec![
+    ///     StorageTree::node("Electives", vec![
+    ///         module("Flight System Dynamics", "Equations of motion"),
+    ///         module("Rocket Propulsion", "Nozzles"),
+    ///     ]),
+    ///     module("Master's Thesis", "Flight tests"),
+    /// ]);
+    ///
+    /// let results = Results::of(&tree, " flight ");
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results.get(&vec![0, 0])[0].field, Field::Name);
+    /// assert_eq!(results.get(&vec![1])[0].field, Field::Content);
+    /// assert!(results.get(&vec![0, 1]).is_empty());
+    /// assert!(results.is_visible(&vec![0]));
+    /// assert!(!results.is_visible(&vec![0, 1]));
+    /// ```
+    pub fn of<K>(tree: &StorageTree<K, Module>, query: &str) -> Self {
+        Self::of_index(&Index::from_tree(tree), query, |path| {
+            match tree.get(path)? {
+                StorageTree::Leaf(module) => Some((path.clone(), module)),
+                StorageTree::Node(_) => None,
+            }
+        })
+    }
+
+    /// Same as [of](Results::of), with an index of the modules that is kept up to date.
+    ///
+    /// The keys of the index are looked up to get the path and the current version of each module.
+    pub fn of_index<'a, K: Ord + Clone>(
+        index: &Index<K>,
+        query: &str,
+        lookup: impl Fn(&K) -> Option<(Path, &'a Module)>,
+    ) -> Self {
+        let terms: Vec<String> = tokenize(query).map(|(_, term)| term).collect();
+        let mut results = Self::default();
+        for hit in index.search(query) {
+            let Some((path, module)) = lookup(&hit.key) else {
+                continue;
+            };
+            let matches = hit
+                .fields
+                .into_iter()
+                .filter_map(|field| {
+                    let text = text(module, field)?;
+                    let range = find_term(&text, &terms)?;
+                    Some(Match { field, text, range })
+                })
+                .collect();
+            for len in 0..=path.len() {
+                results.visible.insert(path[..len].to_vec());
+            }
+            results.ranking.push(path.clone());
+            results.matches.insert(path, matches);
+        }
+        results
+    }
+
+    /// The matches of the module at the given path. Empty if it does not match.
+    pub fn get(&self, path: &Path) -> &[Match] {
+        self.matches.get(path).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Indicates whether the node at the given path is a matching module, or contains one.
+    pub fn is_visible(&self, path: &Path) -> bool {
+        self.visible.contains(path)
+    }
+
+    /// The paths of the matching modules, the best match first.
+    pub fn ranking(&self) -> &[Path] {
+        &self.ranking
+    }
+
+    /// Number of matching modules.
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// Indicates whether no module matches.
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+}
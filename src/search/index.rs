@@ -0,0 +1,276 @@
+//! Inverted index of the words of modules, to search large catalogues quickly.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::{Bound, Range},
+};
+
+use crate::{
+    module::{Field, Module},
+    storage_tree::{Path, StorageTree},
+};
+
+use super::text;
+
+/// Lowercases a word and spells out umlauts and ß, so that "Größe" and "GROESSE" are the same word.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::search::normalize;
+///
+/// assert_eq!(normalize("Größe"), "groesse");
+/// assert_eq!(normalize("GROESSE"), "groesse");
+/// assert_eq!(normalize("Übung"), "uebung");
+/// ```
+pub fn normalize(word: &str) -> String {
+    let mut normalized = String::with_capacity(word.len());
+    for c in word.chars().flat_map(char::to_lowercase) {
+        match c {
+            'ä' => normalized.push_str("ae"),
+            'ö' => normalized.push_str("oe"),
+            'ü' => normalized.push_str("ue"),
+            'ß' => normalized.push_str("ss"),
+            c => normalized.push(c),
+        }
+    }
+    normalized
+}
+
+/// Splits a text into its [normalized](normalize) words, with their position in the text, in bytes.
+///
+/// A word is a sequence of letters and digits.
+///
+/// # Example
+/// ```
+/// use tum_module_picker::search::tokenize;
+///
+/// let words: Vec<_> = tokenize("Flight-System Dynamik (MW2138)").collect();
+/// assert_eq!(words, vec![
+///     (0..6, "flight".to_string()),
+///     (7..13, "system".to_string()),
+///     (14..21, "dynamik".to_string()),
+///     (23..29, "mw2138".to_string()),
+/// ]);
+/// ```
+pub fn tokenize(text: &str) -> impl Iterator<Item = (Range<usize>, String)> + '_ {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices() {
+        if c.is_alphanumeric() {
+            start.get_or_insert(index);
+        } else if let Some(start) = start.take() {
+            words.push(start..index)
+        }
+    }
+    if let Some(start) = start {
+        words.push(start..text.len())
+    }
+    words
+        .into_iter()
+        .map(|range| (range.clone(), normalize(&text[range])))
+}
+
+/// Finds the first word of the text starting with one of the [normalized](normalize) terms.
+///
+/// Returns the position of the word in the text, in bytes.
+pub fn find_term(text: &str, terms: &[String]) -> Option<Range<usize>> {
+    tokenize(text)
+        .find(|(_, word)| terms.iter().any(|term| word.starts_with(term.as_str())))
+        .map(|(range, _)| range)
+}
+
+/// How much a word found in a field counts, compared to a word of the description.
+fn weight(field: Field) -> f32 {
+    use Field::*;
+    match field {
+        Name | Id => 4.,
+        Abbreviation | Subtitle | Courses | Exams => 2.,
+        _ => 1.,
+    }
+}
+
+/// How much a word that only starts with a term counts, compared to the term itself.
+const PREFIX_WEIGHT: f32 = 0.5;
+
+/// A document found by [Index::search].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit<K> {
+    /// Key of the document.
+    pub key: K,
+    /// Relevance of the document. Higher is better.
+    pub score: f32,
+    /// The fields containing a term of the query, in the order of [Field::ALL].
+    pub fields: Vec<Field>,
+}
+
+/// Inverted index of the words of modules, each given by a key of type `K`.
+///
+/// Modules can be added, updated and removed one by one, so that the index is kept up to date
+/// while they are edited.
+#[derive(Debug, Clone)]
+pub struct Index<K> {
+    /// For each word, the documents containing it, and how many times it appears in each field.
+    postings: BTreeMap<String, BTreeMap<K, Vec<(Field, u32)>>>,
+    /// The words of each document.
+    documents: BTreeMap<K, BTreeSet<String>>,
+}
+
+impl<K> Default for Index<K> {
+    fn default() -> Self {
+        Self {
+            postings: BTreeMap::new(),
+            documents: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Ord + Clone> Index<K> {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a module to the index, replacing the one with the same key.
+    pub fn insert(&mut self, key: K, module: &Module) {
+        self.remove(&key);
+        let mut counts: BTreeMap<String, Vec<(Field, u32)>> = BTreeMap::new();
+        for field in Field::ALL {
+            let Some(text) = text(module, field) else {
+                continue;
+            };
+            for (_, word) in tokenize(&text) {
+                let fields = counts.entry(word).or_default();
+                match fields.iter_mut().find(|(found, _)| *found == field) {
+                    Some((_, count)) => *count += 1,
+                    None => fields.push((field, 1)),
+                }
+            }
+        }
+        let words = counts.keys().cloned().collect();
+        for (word, fields) in counts {
+            self.postings
+                .entry(word)
+                .or_default()
+                .insert(key.clone(), fields);
+        }
+        self.documents.insert(key, words);
+    }
+
+    /// Removes the module with the given key. Returns whether it was in the index.
+    pub fn remove(&mut self, key: &K) -> bool {
+        let Some(words) = self.documents.remove(key) else {
+            return false;
+        };
+        for word in words {
+            if let Some(documents) = self.postings.get_mut(&word) {
+                documents.remove(key);
+                if documents.is_empty() {
+                    self.postings.remove(&word);
+                }
+            }
+        }
+        true
+    }
+
+    /// Number of modules in the index.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Indicates whether the index has no module.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Finds the modules containing every word of the query, the best ones first.
+    ///
+    /// The words of the query are [normalized](normalize), and each of them also matches the words
+    /// starting with it, so that results show up while typing. A module ranks higher when the words
+    /// are in its name or id, when they appear often in it, and when few other modules contain them.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::{module::{Field, Module}, search::Index};
+    ///
+    /// let module = |name: &str, content: &str| {
+    ///     let mut module = Module::default();
+    ///     module.name = name.to_string();
+    ///     module.content = content.to_string();
+    ///     module
+    /// };
+    ///
+    /// let mut index = Index::new();
+    /// index.insert(1, &module("Flugsystemdynamik", "Flugzeuge und ihre Dynamik"));
+    /// index.insert(2, &module("Raumfahrt", "Flugbahnen von Satelliten"));
+    /// index.insert(3, &module("Strömungslehre", "Grenzschichten"));
+    ///
+    /// let hits = index.search("flug");
+    /// assert_eq!(hits.iter().map(|hit| hit.key).collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(hits[0].fields, vec![Field::Name, Field::Content]);
+    ///
+    /// assert_eq!(index.search("stroemung")[0].key, 3);
+    /// assert_eq!(index.search("flug satelliten")[0].key, 2);
+    ///
+    /// index.insert(2, &module("Raumfahrt", "Orbits"));
+    /// assert_eq!(index.search("flug").len(), 1);
+    /// ```
+    pub fn search(&self, query: &str) -> Vec<Hit<K>> {
+        let terms: Vec<String> = tokenize(query).map(|(_, term)| term).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let total = self.documents.len() as f32;
+        let mut hits: BTreeMap<&K, (usize, f32, Vec<Field>)> = BTreeMap::new();
+        for (rank, term) in terms.iter().enumerate() {
+            let words = self
+                .postings
+                .range::<str, _>((Bound::Included(term.as_str()), Bound::Unbounded))
+                .take_while(|(word, _)| word.starts_with(term.as_str()));
+            for (word, documents) in words {
+                let idf = (1. + total / documents.len() as f32).ln();
+                let exact = if word == term { 1. } else { PREFIX_WEIGHT };
+                for (key, fields) in documents {
+                    let hit = hits.entry(key).or_insert((0, 0., Vec::new()));
+                    // Only the documents that contain all the previous terms are kept.
+                    if hit.0 < rank {
+                        continue;
+                    }
+                    hit.0 = rank + 1;
+                    for (field, count) in fields {
+                        hit.1 += weight(*field) * (1. + (*count as f32).ln()) * idf * exact;
+                        if !hit.2.contains(field) {
+                            hit.2.push(*field)
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut hits: Vec<Hit<K>> = hits
+            .into_iter()
+            .filter(|(_, (matched, _, _))| *matched == terms.len())
+            .map(|(key, (_, score, mut fields))| {
+                fields.sort_by_key(|field| Field::ALL.iter().position(|f| f == field));
+                Hit {
+                    key: key.clone(),
+                    score,
+                    fields,
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits
+    }
+}
+
+impl Index<Path> {
+    /// Indexes every module of a tree, by path.
+    pub fn from_tree<K>(tree: &StorageTree<K, Module>) -> Self {
+        let mut index = Self::new();
+        for (path, module) in tree.leaf_paths() {
+            index.insert(path, module)
+        }
+        index
+    }
+}
@@ -3,8 +3,10 @@
 //! Each tree node has a key to make navigating possible.
 //! Also provides [iced] helpers to turn a tree in an [Element](iced::Element).
 
+use serde::{Deserialize, Serialize};
+
 /// A storage tree with nodes of type `K` (Key) and leafs of type `T`.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StorageTree<K, T> {
     /// A leaf.
     Leaf(T),
@@ -13,7 +15,7 @@ pub enum StorageTree<K, T> {
 }
 
 /// A tree node that has a key and children [StorageTree]s.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Node<K, T> {
     key: K,
     children: Vec<StorageTree<K, T>>,
@@ -72,6 +74,94 @@ impl<K, T> StorageTree<K, T> {
         })
     }
 
+    /// Inserts a sub tree among the children of the node pointed by `parent`, at the given index.
+    ///
+    /// If the index is past the last child, the sub tree is appended.
+    /// If successful, returns the path of the inserted tree. Otherwise the sub tree is given back.
+    pub fn insert(&mut self, other: Self, parent: &Path, index: usize) -> Result<Path, Self> {
+        match self.get_mut(parent) {
+            Some(StorageTree::Node(node)) => {
+                let index = index.min(node.children.len());
+                node.children.insert(index, other);
+                let mut path = parent.clone();
+                path.push(index);
+                Ok(path)
+            }
+            _ => Err(other),
+        }
+    }
+
+    /// Removes the sub tree at the given path and returns it.
+    ///
+    /// The root can't be removed. The paths of the following siblings change,
+    /// see [path_after_removal].
+    pub fn remove(&mut self, path: &Path) -> Option<Self> {
+        let (index, parent) = path.split_last()?;
+        match self.get_mut(&parent.to_vec())? {
+            StorageTree::Leaf(_) => None,
+            StorageTree::Node(node) => {
+                (*index < node.children.len()).then(|| node.children.remove(*index))
+            }
+        }
+    }
+
+    /// Moves the sub tree at `from` among the children of the node at `to`, at the given index.
+    ///
+    /// The index is the one among the children once the sub tree has been removed from its former place.
+    /// Fails if `from` is the root, if `to` is not a node, or if `to` is inside the moved sub tree.
+    /// If successful, returns the new path of the sub tree.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::storage_tree::StorageTree;
+    ///
+    /// let mut tree = StorageTree::node("root", vec![
+    ///     StorageTree::leaf(1),
+    ///     StorageTree::node("folder", vec![StorageTree::leaf(2)]),
+    /// ]);
+    ///
+    /// // The folder moves up once the leaf before it is gone.
+    /// let path = tree.move_to(&vec![0], &vec![1], 0).unwrap();
+    /// assert_eq!(path, vec![0, 0]);
+    /// let leafs: Vec<_> = tree.leaf_paths().into_iter().map(|(_, leaf)| *leaf).collect();
+    /// assert_eq!(leafs, vec![1, 2]);
+    ///
+    /// assert!(tree.move_to(&vec![0], &vec![0], 0).is_none());
+    /// ```
+    pub fn move_to(&mut self, from: &Path, to: &Path, index: usize) -> Option<Path> {
+        if from.is_empty() || to.starts_with(from) {
+            return None;
+        }
+        if !matches!(self.get(to), Some(StorageTree::Node(_))) {
+            return None;
+        }
+        let tree = self.remove(from)?;
+        let to = path_after_removal(to, from).expect("to is not inside from");
+        self.insert(tree, &to, index).ok()
+    }
+
+    /// Swaps two sub trees that have the same parent.
+    ///
+    /// Returns whether they were swapped.
+    pub fn swap(&mut self, a: &Path, b: &Path) -> bool {
+        let (Some((a_index, a_parent)), Some((b_index, b_parent))) = (a.split_last(), b.split_last())
+        else {
+            return false;
+        };
+        if a_parent != b_parent {
+            return false;
+        }
+        match self.get_mut(&a_parent.to_vec()) {
+            Some(StorageTree::Node(node))
+                if *a_index < node.children.len() && *b_index < node.children.len() =>
+            {
+                node.children.swap(*a_index, *b_index);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Applies a function to all the keys of the [StorageTree].
     ///
     /// The function is called once per key, and in an arbitrary order.
@@ -88,6 +178,76 @@ impl<K, T> StorageTree<K, T> {
         }
     }
 
+    /// Builds a new [StorageTree] with the same shape by applying `f` to the keys and `g` to the leafs.
+    ///
+    /// Unlike [map_keys](Self::map_keys), the tree is only borrowed.
+    pub fn map_ref<L, U, F: Fn(&K) -> L, G: Fn(&T) -> U>(&self, f: &F, g: &G) -> StorageTree<L, U> {
+        match self {
+            StorageTree::Leaf(value) => StorageTree::leaf(g(value)),
+            StorageTree::Node(node) => StorageTree::node(
+                f(&node.key),
+                node.children.iter().map(|tree| tree.map_ref(f, g)).collect(),
+            ),
+        }
+    }
+
+    /// Computes a value for every sub tree, from the bottom up, and returns them in a tree of the same shape.
+    ///
+    /// `leaf` computes the value of a leaf, and `node` the value of a node from its key and the values of its children.
+    /// Each node of the returned tree has its value as key, so values can be found again with [get](Self::get).
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::storage_tree::StorageTree;
+    ///
+    /// let tree = StorageTree::node("root", vec![
+    ///     StorageTree::leaf(1),
+    ///     StorageTree::node("folder", vec![StorageTree::leaf(2), StorageTree::leaf(3)]),
+    /// ]);
+    ///
+    /// let sums = tree.fold_subtrees(&|leaf| *leaf, &|_, children| children.into_iter().sum());
+    ///
+    /// assert_eq!(*sums.value(), 6);
+    /// assert_eq!(*sums.get(&vec![1]).unwrap().value(), 5);
+    /// ```
+    pub fn fold_subtrees<A, L: Fn(&T) -> A, N: Fn(&K, Vec<&A>) -> A>(
+        &self,
+        leaf: &L,
+        node: &N,
+    ) -> StorageTree<A, A> {
+        match self {
+            StorageTree::Leaf(value) => StorageTree::leaf(leaf(value)),
+            StorageTree::Node(tree_node) => {
+                let children: Vec<StorageTree<A, A>> = tree_node
+                    .children
+                    .iter()
+                    .map(|child| child.fold_subtrees(leaf, node))
+                    .collect();
+                let value = node(&tree_node.key, children.iter().map(StorageTree::value).collect());
+                StorageTree::node(value, children)
+            }
+        }
+    }
+
+    /// Lists the leafs of a [StorageTree] with their [Path], in display order.
+    pub fn leaf_paths(&self) -> Vec<(Path, &T)> {
+        let mut res = Vec::new();
+        let mut stack = vec![(Path::new(), self)];
+        while let Some((path, tree)) = stack.pop() {
+            match tree {
+                StorageTree::Leaf(leaf) => res.push((path, leaf)),
+                StorageTree::Node(node) => {
+                    stack.extend(node.children.iter().enumerate().rev().map(|(index, child)| {
+                        let mut path = path.clone();
+                        path.push(index);
+                        (path, child)
+                    }))
+                }
+            }
+        }
+        res
+    }
+
     /// Iterator on the leafs of a [StorageTree].
     pub fn leaf_iter(&self) -> LeafIterator<'_, K, T> {
         LeafIterator { stack: vec![self] }
@@ -119,6 +279,36 @@ impl<K, T> StorageTree<K, T> {
     }
 }
 
+/// Returns what a path becomes once the sub tree at `removed` has been [removed](StorageTree::remove).
+///
+/// Returns [None] if the path was inside the removed sub tree.
+pub fn path_after_removal(path: &Path, removed: &Path) -> Option<Path> {
+    if path.starts_with(removed) {
+        return None;
+    }
+    let mut path = path.clone();
+    if let Some((index, parent)) = removed.split_last()
+        && path.len() > parent.len()
+        && path.starts_with(parent)
+        && path[parent.len()] > *index
+    {
+        path[parent.len()] -= 1;
+    }
+    Some(path)
+}
+
+impl<A> StorageTree<A, A> {
+    /// Returns the value of a leaf, or the key of a node.
+    ///
+    /// This is the value of a sub tree for trees built by [fold_subtrees](Self::fold_subtrees).
+    pub fn value(&self) -> &A {
+        match self {
+            StorageTree::Leaf(value) => value,
+            StorageTree::Node(node) => &node.key,
+        }
+    }
+}
+
 impl<K, T> Node<K, T> {
     /// Returns a reference to the key of a node.
     pub fn get_key(&self) -> &K {
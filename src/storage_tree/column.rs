@@ -7,10 +7,11 @@ use std::{
 
 use iced::{
     Element,
-    Length::{self, Shrink},
-    Pixels,
+    Length::{self, Fill, Shrink},
+    Pixels, Theme,
     alignment::Vertical,
-    widget::{Space, button, column, horizontal_space, row},
+    mouse,
+    widget::{Space, button, column, container, horizontal_space, mouse_area, row, text},
 };
 use iced_fonts::required::{RequiredIcons, to_text};
 
@@ -27,13 +28,30 @@ pub struct Column<'a, K, T, Message> {
     value_to_element: Box<dyn Fn(&'a T, Path) -> Element<'a, Message> + 'a>,
 
     spacing: Pixels,
+    draggable: bool,
+    filter: Option<Filter<'a>>,
 }
 
+/// Tells whether the node at a path is shown.
+type Filter<'a> = Box<dyn Fn(&Path) -> bool + 'a>;
+
+/// Height of the drop zones between siblings while a node is dragged.
+const DROP_GAP: f32 = 8.;
+
 /// Content and state of a [Column]
 #[derive(Debug)]
 pub struct Content<K, T> {
     st: StorageTree<MetaKey<K, NodeState>, T>,
     just_extend_on_select: bool,
+    drag: Option<Drag>,
+}
+
+/// A node being dragged.
+#[derive(Debug, Clone)]
+struct Drag {
+    from: Path,
+    /// Where it would be dropped: the path of a node and the index among its children.
+    target: Option<(Path, usize)>,
 }
 
 impl<K, T> Content<K, T> {
@@ -43,6 +61,7 @@ impl<K, T> Content<K, T> {
         Self {
             st,
             just_extend_on_select: true,
+            drag: None,
         }
     }
 
@@ -59,6 +78,28 @@ impl<K, T> Content<K, T> {
                     !self.get_expanded(&path).unwrap_or(true)
                 },
             ),
+            Action::Grab(from) => self.drag = Some(Drag { from, target: None }),
+            Action::DragOver { to, index } => {
+                if let Some(drag) = &mut self.drag {
+                    drag.target = Some((to, index))
+                }
+            }
+            Action::DragOut { to, index } => {
+                // The pointer may already be over the next target.
+                if let Some(drag) = &mut self.drag
+                    && drag.target == Some((to, index))
+                {
+                    drag.target = None
+                }
+            }
+            Action::Move { from, to, index } => {
+                self.drag = None;
+                if let Some(mut path) = self.move_to(&from, &to, index) {
+                    path.pop();
+                    self.set_expanded(&path, true)
+                }
+            }
+            Action::CancelDrag => self.drag = None,
         }
     }
 
@@ -92,6 +133,51 @@ impl<K, T> Content<K, T> {
     pub fn expand_all(&mut self, value: bool) {
         self.st.mut_key_iter().map(|meta_key| meta_key.get_mut_metadata().expanded = value).collect()
     }
+
+    /// Returns a copy of the displayed [StorageTree], without the GUI state of the nodes.
+    pub fn to_storage_tree(&self) -> StorageTree<K, T>
+    where
+        K: Clone,
+        T: Clone,
+    {
+        self.st.map_ref(&|meta_key| (**meta_key).clone(), &T::clone)
+    }
+
+    /// Replaces the displayed [StorageTree], for example to restore a previous version of it.
+    ///
+    /// The nodes that are at the same path with the same key as before keep their expanded status.
+    pub fn replace_tree(&mut self, storage_tree: StorageTree<K, T>)
+    where
+        K: PartialEq,
+    {
+        let mut st = storage_tree.map_keys(&|key| MetaKey::new(key, NodeState::new()));
+        keep_expanded(&mut st, &self.st, &mut Vec::new());
+        self.st = st;
+        self.drag = None;
+    }
+}
+
+/// Copies the expanded status of the nodes of `old` to the nodes of `new` at the same path with the same key.
+///
+/// `path` is the path of `new` in the whole tree.
+fn keep_expanded<K: PartialEq, T>(
+    new: &mut StorageTree<MetaKey<K, NodeState>, T>,
+    old: &StorageTree<MetaKey<K, NodeState>, T>,
+    path: &mut Path,
+) {
+    let StorageTree::Node(node) = new else {
+        return;
+    };
+    if let Some(StorageTree::Node(old_node)) = old.get(path)
+        && **old_node.get_key() == **node.get_key()
+    {
+        node.get_mut_key().get_mut_metadata().expanded = old_node.get_key().get_metadata().expanded;
+    }
+    for (index, child) in node.get_mut_children().iter_mut().enumerate() {
+        path.push(index);
+        keep_expanded(child, old, path);
+        path.pop();
+    }
 }
 
 struct Icons<'a, Message> {
@@ -125,6 +211,35 @@ pub enum Action {
     ///
     /// Feel free to intercept this message and do something before forwarding it to [perform](Content::perform)
     Selected(Path),
+    /// Starts dragging the node at the given path.
+    Grab(Path),
+    /// The dragged node would be dropped among the children of the node at `to`, at `index`.
+    DragOver {
+        #[allow(missing_docs)]
+        to: Path,
+        #[allow(missing_docs)]
+        index: usize,
+    },
+    /// The pointer left a place given by [DragOver](Action::DragOver), so nothing is dropped there.
+    DragOut {
+        #[allow(missing_docs)]
+        to: Path,
+        #[allow(missing_docs)]
+        index: usize,
+    },
+    /// Moves the node at `from` among the children of the node at `to`, at `index`.
+    ///
+    /// See [move_to](StorageTree::move_to) for the meaning of the index.
+    Move {
+        #[allow(missing_docs)]
+        from: Path,
+        #[allow(missing_docs)]
+        to: Path,
+        #[allow(missing_docs)]
+        index: usize,
+    },
+    /// Stops dragging without moving anything.
+    CancelDrag,
 }
 
 impl<'a, K, T, Message> Column<'a, K, T, Message> {
@@ -143,6 +258,8 @@ impl<'a, K, T, Message> Column<'a, K, T, Message> {
             key_to_element: Box::new(key_to_element),
             value_to_element: Box::new(value_to_element),
             spacing: 0.into(),
+            draggable: false,
+            filter: None,
         }
     }
 
@@ -210,30 +327,84 @@ impl<'a, K, T, Message> Column<'a, K, T, Message> {
             ..self
         }
     }
+
+    /// Adds a handle to every node but the root, to drag it onto a folder or between siblings.
+    ///
+    /// Dropping it emits an [Action::Move].
+    pub fn draggable(self) -> Self {
+        Self {
+            draggable: true,
+            ..self
+        }
+    }
+
+    /// Only shows the nodes at the paths for which `visible` returns true, for example the results of a search.
+    ///
+    /// The children of a hidden node are hidden too. The visible nodes are shown expanded.
+    pub fn filter(self, visible: impl Fn(&Path) -> bool + 'a) -> Self {
+        Self {
+            filter: Some(Box::new(visible)),
+            ..self
+        }
+    }
+}
+
+/// Handle that starts dragging the node at the given path.
+fn drag_handle<'a, Message: Clone + 'a>(
+    path: &Path,
+    on_interaction: &impl Fn(Action) -> Message,
+) -> Element<'a, Message> {
+    mouse_area(text("≡"))
+        .on_press(on_interaction(Action::Grab(path.clone())))
+        .interaction(mouse::Interaction::Grab)
+        .into()
+}
+
+/// Style of the drop indicator and of the folder the dragged node would be dropped on.
+fn drop_style(theme: &Theme) -> container::Style {
+    container::Style::default().background(theme.palette().primary.scale_alpha(0.5))
 }
 
 impl<K, T> StorageTree<MetaKey<K, NodeState>, T> {
-    /// Draws the content
-    fn to_element<'a, Message: Clone + 'a, Oi, Ok, Ov>(
+    /// Draws the content, with the settings of the column it belongs to.
+    fn to_element<'a, Message: Clone + 'a>(
         &'a self,
-        icons: &Option<Icons<'a, Message>>,
-        space: f32,
-        spacing: Pixels,
-        on_interaction: &Oi,
-        key_to_element: &Ok,
-        value_to_element: &Ov,
+        column: &Column<'a, K, T, Message>,
         current_path: Path,
-    ) -> Element<'a, Message>
-    where
-        Oi: Fn(Action) -> Message,
-        Ok: Fn(&'a Node<MetaKey<K, NodeState>, T>, Path) -> Element<'a, Message>,
-        Ov: Fn(&'a T, Path) -> Element<'a, Message>,
-    {
+    ) -> Element<'a, Message> {
+        let Column {
+            icons,
+            space,
+            spacing,
+            on_interaction,
+            key_to_element,
+            value_to_element,
+            ..
+        } = column;
+        let (space, spacing) = (*space, *spacing);
+        // `drag` is `None` if the column is not draggable, and `Some(None)` if nothing is dragged.
+        let drag = column.draggable.then_some(column.content.drag.as_ref());
+        let filter = column.filter.as_deref();
+        let handle = match drag {
+            Some(_) if !current_path.is_empty() => Some(drag_handle(&current_path, on_interaction)),
+            _ => None,
+        };
+        // Where the dragged node can be dropped: not inside itself.
+        let dragged = drag
+            .flatten()
+            .filter(|drag| !current_path.starts_with(&drag.from));
+
         match &self {
-            StorageTree::Leaf(content) => value_to_element(content, current_path),
+            StorageTree::Leaf(content) => match handle {
+                Some(handle) => row![handle, value_to_element(content, current_path)]
+                    .spacing(spacing.0.max(5.))
+                    .align_y(Vertical::Center)
+                    .into(),
+                None => value_to_element(content, current_path),
+            },
             StorageTree::Node(node) => {
                 let metakey = node.get_key();
-                let expanded = metakey.get_metadata().expanded;
+                let expanded = metakey.get_metadata().expanded || filter.is_some();
 
                 let key_element: Element<'_, _> =
                     button(key_to_element(node, current_path.clone()))
@@ -257,26 +428,81 @@ impl<K, T> StorageTree<MetaKey<K, NodeState>, T> {
                     }
                     None => key_element,
                 };
+                let row: Element<'a, Message> = match handle {
+                    Some(handle) => row![handle, row]
+                        .spacing(spacing.0.max(5.))
+                        .align_y(Vertical::Center)
+                        .into(),
+                    None => row,
+                };
+                let children = node.get_children().len();
+                let row = match dragged {
+                    Some(drag) => {
+                        let target =
+                            drag.target.as_ref() == Some(&(current_path.clone(), children));
+                        mouse_area(container(row).style(move |theme| {
+                            if target {
+                                drop_style(theme)
+                            } else {
+                                container::Style::default()
+                            }
+                        }))
+                        .on_enter(on_interaction(Action::DragOver {
+                            to: current_path.clone(),
+                            index: children,
+                        }))
+                        .on_exit(on_interaction(Action::DragOut {
+                            to: current_path.clone(),
+                            index: children,
+                        }))
+                        .into()
+                    }
+                    None => row,
+                };
 
                 if expanded {
-                    let expansion = node.get_children().iter().enumerate().map(|(idx, tree)| {
-                        let mut new_path = current_path.clone();
-                        new_path.push(idx);
-                        tree.to_element(
-                            icons,
-                            space,
-                            spacing,
-                            on_interaction,
-                            key_to_element,
-                            value_to_element,
-                            new_path,
+                    // Drop zone before the child at the given index, or after the last one.
+                    let gap = |index: usize| -> Option<Element<'a, Message>> {
+                        let drag = dragged?;
+                        let target = drag.target.as_ref() == Some(&(current_path.clone(), index));
+                        Some(
+                            mouse_area(
+                                container(Space::new(Fill, if target { 2. } else { 0. }))
+                                    .style(drop_style)
+                                    .center_y(DROP_GAP),
+                            )
+                            .on_enter(on_interaction(Action::DragOver {
+                                to: current_path.clone(),
+                                index,
+                            }))
+                            .on_exit(on_interaction(Action::DragOut {
+                                to: current_path.clone(),
+                                index,
+                            }))
+                            .into(),
                         )
-                    });
+                    };
+                    let expansion =
+                        node.get_children()
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(idx, tree)| {
+                                let mut new_path = current_path.clone();
+                                new_path.push(idx);
+                                filter
+                                    .is_none_or(|visible| visible(&new_path))
+                                    .then_some((idx, tree, new_path))
+                            })
+                            .flat_map(|(idx, tree, new_path)| {
+                                let child = tree.to_element(column, new_path);
+                                gap(idx).into_iter().chain([child])
+                            });
                     column![
                         row,
                         row![
                             horizontal_space().width(Length::Fixed(space)),
-                            iced::widget::Column::from_iter(expansion).spacing(spacing)
+                            iced::widget::Column::from_iter(expansion.chain(gap(children)))
+                                .spacing(spacing)
                         ],
                     ]
                     .spacing(spacing)
@@ -291,15 +517,40 @@ impl<K, T> StorageTree<MetaKey<K, NodeState>, T> {
 
 impl<'a, K, T, Message: Clone + 'a> From<Column<'a, K, T, Message>> for Element<'a, Message> {
     fn from(value: Column<'a, K, T, Message>) -> Self {
-        value.content.to_element(
-            &value.icons,
-            value.space,
-            value.spacing,
-            &value.on_interaction,
-            &value.key_to_element,
-            &value.value_to_element,
-            Vec::new(),
-        )
+        let drag = value.content.drag.as_ref();
+        let element = value.content.to_element(&value, Vec::new());
+
+        match drag {
+            Some(drag) if value.draggable => {
+                let drop = match &drag.target {
+                    Some((to, index)) => {
+                        // The index is given before the node is removed from its former place.
+                        let (index, unmoved) = match drag.from.split_last() {
+                            Some((from, parent)) if parent == to.as_slice() => {
+                                let index = if index > from { index - 1 } else { *index };
+                                (index, index == *from)
+                            }
+                            _ => (*index, false),
+                        };
+                        if unmoved {
+                            Action::CancelDrag
+                        } else {
+                            Action::Move {
+                                from: drag.from.clone(),
+                                to: to.clone(),
+                                index,
+                            }
+                        }
+                    }
+                    None => Action::CancelDrag,
+                };
+                mouse_area(element)
+                    .on_release((value.on_interaction)(drop))
+                    .interaction(mouse::Interaction::Grabbing)
+                    .into()
+            }
+            _ => element,
+        }
     }
 }
 
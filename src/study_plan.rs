@@ -0,0 +1,212 @@
+//! The study plan, telling in which semester each module will be taken.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::module::{ECTS, Module, semester::Semester};
+
+/// Semester in which each module, given by its id, is planned.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StudyPlan {
+    semesters: BTreeMap<String, Semester>,
+}
+
+/// Reason why a module should not be taken in the semester it is planned in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanIssue {
+    /// The module is only valid from a later semester.
+    NotYetValid(Semester),
+    /// The module is not valid anymore after the given semester.
+    NoLongerValid(Semester),
+    /// A course of the module is only offered in the other season.
+    NotOffered {
+        /// Title of the course.
+        course: String,
+        /// Semester in which the course is offered.
+        offered_in: Semester,
+    },
+}
+
+impl Display for PlanIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanIssue::NotYetValid(semester) => write!(f, "Only valid from {semester}"),
+            PlanIssue::NoLongerValid(semester) => write!(f, "Only valid until {semester}"),
+            PlanIssue::NotOffered { course, offered_in } => write!(
+                f,
+                "{course} is offered in {} semesters",
+                if *offered_in.get_winter() {
+                    "winter"
+                } else {
+                    "summer"
+                }
+            ),
+        }
+    }
+}
+
+impl StudyPlan {
+    /// Creates an empty plan.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the semester in which a module is planned, if any.
+    pub fn get(&self, id: &str) -> Option<&Semester> {
+        self.semesters.get(id)
+    }
+
+    /// Plans a module for a semester, replacing the previous one.
+    pub fn assign(&mut self, id: String, semester: Semester) {
+        self.semesters.insert(id, semester);
+    }
+
+    /// Removes a module from the plan, returning the semester it was planned in.
+    pub fn remove(&mut self, id: &str) -> Option<Semester> {
+        self.semesters.remove(id)
+    }
+
+    /// Iterator over the planned modules, as module id and semester.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Semester)> {
+        self.semesters.iter()
+    }
+
+    /// The semesters in which at least one module is planned, in chronological order.
+    pub fn semesters(&self) -> Vec<Semester> {
+        let mut semesters: Vec<Semester> = Vec::new();
+        for semester in self.semesters.values() {
+            if !semesters.contains(semester) {
+                semesters.push(semester.clone())
+            }
+        }
+        semesters.sort();
+        semesters
+    }
+
+    /// The ids of the modules planned in a semester.
+    pub fn modules_in<'a>(&'a self, semester: &'a Semester) -> impl Iterator<Item = &'a String> {
+        self.semesters
+            .iter()
+            .filter(move |(_, planned)| *planned == semester)
+            .map(|(id, _)| id)
+    }
+
+    /// Sums up the ECTS credits of the planned modules in each semester, in chronological order.
+    ///
+    /// The modules are looked up by id among the given ones. A module given twice is counted once.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::{module::{Module, semester::Semester}, study_plan::StudyPlan};
+    ///
+    /// let mut dynamics = Module::default();
+    /// dynamics.id = "MW2138".to_string();
+    /// dynamics.ects = 5;
+    /// let mut control = Module::default();
+    /// control.id = "MW0537".to_string();
+    /// control.ects = 6;
+    /// let mut thesis = Module::default();
+    /// thesis.id = "MW2000".to_string();
+    /// thesis.ects = 30;
+    ///
+    /// let mut plan = StudyPlan::new();
+    /// plan.assign("MW2000".to_string(), Semester::new(2026, true));
+    /// plan.assign("MW2138".to_string(), Semester::new(2025, true));
+    /// plan.assign("MW0537".to_string(), Semester::new(2025, true));
+    ///
+    /// let totals = plan.ects([&dynamics, &control, &thesis, &dynamics]);
+    /// assert_eq!(totals, vec![(Semester::new(2025, true), 11), (Semester::new(2026, true), 30)]);
+    /// ```
+    pub fn ects<'a>(&self, modules: impl IntoIterator<Item = &'a Module>) -> Vec<(Semester, ECTS)> {
+        let mut totals: Vec<(Semester, ECTS)> = self
+            .semesters()
+            .into_iter()
+            .map(|semester| (semester, 0))
+            .collect();
+        let mut counted = BTreeSet::new();
+        for module in modules {
+            let Some(semester) = self.get(&module.id) else {
+                continue;
+            };
+            if !counted.insert(&module.id) {
+                continue;
+            }
+            if let Some((_, total)) = totals.iter_mut().find(|(planned, _)| planned == semester) {
+                *total += module.ects
+            }
+        }
+        totals
+    }
+
+    /// Checks that a module can be taken in a semester.
+    ///
+    /// The module must be valid in the semester, and its courses are assumed to be offered
+    /// every year in the season of their [offered_in](crate::module::course::Course::get_offered_in) semester.
+    /// Courses whose semester is unknown are not checked.
+    ///
+    /// # Example
+    /// ```
+    /// use tum_module_picker::{
+    ///     module::{Module, course::Course, semester::Semester},
+    ///     study_plan::{PlanIssue, StudyPlan},
+    /// };
+    ///
+    /// let mut module = Module::default();
+    /// module.valid_until = Some(Semester::new(2026, false));
+    /// let mut course = Course::new("Flight System Dynamics".to_string(), String::new());
+    /// *course.get_mut_offered_in() = Some(Semester::new(2025, true));
+    /// module.courses.push(course);
+    ///
+    /// assert!(StudyPlan::check(&module, &Semester::new(2025, true)).is_empty());
+    /// assert_eq!(
+    ///     StudyPlan::check(&module, &Semester::new(2026, true)),
+    ///     vec![PlanIssue::NoLongerValid(Semester::new(2026, false))]
+    /// );
+    /// assert_eq!(StudyPlan::check(&module, &Semester::new(2026, false)).len(), 1);
+    /// ```
+    pub fn check(module: &Module, semester: &Semester) -> Vec<PlanIssue> {
+        let mut issues = Vec::new();
+        if let Some(from) = &module.valid_from
+            && semester < from
+        {
+            issues.push(PlanIssue::NotYetValid(from.clone()))
+        }
+        if let Some(until) = &module.valid_until
+            && semester > until
+        {
+            issues.push(PlanIssue::NoLongerValid(until.clone()))
+        }
+        for course in &module.courses {
+            if let Some(offered_in) = course.get_offered_in()
+                && offered_in.get_winter() != semester.get_winter()
+            {
+                issues.push(PlanIssue::NotOffered {
+                    course: course.get_title().clone(),
+                    offered_in: offered_in.clone(),
+                })
+            }
+        }
+        issues
+    }
+
+    /// Checks every planned module among the given ones, see [check](StudyPlan::check).
+    ///
+    /// Returns the modules that have issues, with their issues.
+    pub fn issues<'a>(
+        &self,
+        modules: impl IntoIterator<Item = &'a Module>,
+    ) -> Vec<(&'a Module, Vec<PlanIssue>)> {
+        modules
+            .into_iter()
+            .filter_map(|module| {
+                let issues = Self::check(module, self.get(&module.id)?);
+                (!issues.is_empty()).then_some((module, issues))
+            })
+            .collect()
+    }
+}
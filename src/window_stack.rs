@@ -9,7 +9,7 @@ use iced::{
     advanced::graphics::core::Element, futures::{
         channel::mpsc::{self, SendError},
         sink::SinkExt,
-    }, widget::horizontal_space, window::{self, Id, Settings}, Subscription, Task
+    }, keyboard, widget::horizontal_space, window::{self, Id, Settings}, Event, Subscription, Task
 };
 
 /// The [WindowStack] type. Can be rendered in a [Daemon](iced::daemon::Daemon).
@@ -50,6 +50,8 @@ enum InnerAction<Theme, Renderer> {
     ),
     /// Removes a window.
     Pop(Id),
+    /// A key was pressed while a window had the focus.
+    KeyPress(Id, keyboard::Key, keyboard::Modifiers),
 }
 
 impl<Theme, Renderer> Debug for InnerAction<Theme, Renderer> {
@@ -70,6 +72,12 @@ impl<Theme, Renderer> Debug for InnerAction<Theme, Renderer> {
                 .field(arg2)
                 .finish(),
             Self::Pop(arg0) => f.debug_tuple("Pop").field(arg0).finish(),
+            Self::KeyPress(arg0, arg1, arg2) => f
+                .debug_tuple("KeyPress")
+                .field(arg0)
+                .field(arg1)
+                .field(arg2)
+                .finish(),
         }
     }
 }
@@ -126,7 +134,7 @@ impl<Theme: 'static, Renderer: iced::advanced::Renderer + 'static> WindowStack<T
     pub fn update(&mut self, action: Action<Theme, Renderer>) -> Task<Action<Theme, Renderer>> {
         match action.into() {
             InnerAction::Forward(id, message) => {
-                let messager = Messager::new();
+                let messager = Messager::new(id);
                 let updater = self
                     .stack
                     .get_mut(&id)
@@ -172,6 +180,12 @@ impl<Theme: 'static, Renderer: iced::advanced::Renderer + 'static> WindowStack<T
                     Task::none()
                 }
             }
+            InnerAction::KeyPress(id, key, modifiers) => self
+                .stack
+                .get(&id)
+                .and_then(|(window, _)| window.on_key_press(key, modifiers))
+                .map(|message| Task::done(InnerAction::Forward(id, message).into()))
+                .unwrap_or(Task::none()),
         }
     }
 
@@ -194,8 +208,19 @@ impl<Theme: 'static, Renderer: iced::advanced::Renderer + 'static> WindowStack<T
     }
 
     /// Subscription logic of the stack
+    ///
+    /// Key presses are given to the focused window, even when a widget captured them,
+    /// so that shortcuts work while typing in a text input.
     pub fn subscription(&self) -> Subscription<Action<Theme, Renderer>> {
-        window::close_events().map(|id| InnerAction::Pop(id).into())
+        Subscription::batch([
+            window::close_events().map(|id| InnerAction::Pop(id).into()),
+            iced::event::listen_with(|event, _status, id| match event {
+                Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                    Some(InnerAction::KeyPress(id, key, modifiers).into())
+                }
+                _ => None,
+            }),
+        ])
     }
 }
 
@@ -223,11 +248,11 @@ struct Messager<Theme, Renderer> {
 }
 
 impl<Theme, Renderer> Messager<Theme, Renderer> {
-    fn new() -> Self {
+    fn new(window: Id) -> Self {
         let (sx, rx) = mpsc::channel(100);
         Self {
             reciever: rx,
-            sender: PopupMaker { sender: sx },
+            sender: PopupMaker { sender: sx, window },
         }
     }
 }
@@ -236,16 +261,25 @@ impl<Theme, Renderer> Messager<Theme, Renderer> {
 #[derive(Debug)]
 pub struct PopupMaker<Theme = iced::Theme, Renderer = iced::Renderer> {
     sender: mpsc::Sender<WindowSend<Theme, Renderer>>,
+    window: Id,
 }
 
 impl<Theme, Renderer> Clone for PopupMaker<Theme, Renderer> {
     fn clone(&self) -> Self {
         Self {
             sender: self.sender.clone(),
+            window: self.window,
         }
     }
 }
 
+impl<Theme, Renderer> PopupMaker<Theme, Renderer> {
+    /// Closes the window that recieved this [PopupMaker], along with its popups.
+    pub fn close<T>(&self) -> Task<T> {
+        window::close(self.window)
+    }
+}
+
 impl<Theme: 'static, Renderer: iced::advanced::Renderer + 'static> PopupMaker<Theme, Renderer> {
     /// Creates a new popup from the provided state - task pair and [window::Settings].
     pub fn popup<W: Window<Theme, Renderer> + 'static>(
@@ -289,6 +323,13 @@ trait GenericWindow<Theme, Renderer>: Debug + Send {
 
     /// Returns the title of the window.
     fn title(&self) -> String;
+
+    /// Generic keyboard shortcut logic.
+    fn on_key_press(
+        &self,
+        key: keyboard::Key,
+        modifiers: keyboard::Modifiers,
+    ) -> Option<Box<dyn Message>>;
 }
 
 /// The requirement for a type to be a Message
@@ -312,6 +353,17 @@ pub trait Window<Theme = iced::Theme, Renderer = iced::Renderer>: Send + Debug +
 
     /// Sets the title of the application.
     fn title(&self) -> String;
+
+    /// The message to send when a key is pressed while the window has the focus, if any.
+    ///
+    /// Used for keyboard shortcuts. By default, key presses are ignored.
+    fn on_key_press(
+        &self,
+        _key: keyboard::Key,
+        _modifiers: keyboard::Modifiers,
+    ) -> Option<Self::Message> {
+        None
+    }
 }
 
 impl<Theme, Renderer: iced::advanced::Renderer, T: Window<Theme, Renderer>>
@@ -342,4 +394,13 @@ impl<Theme, Renderer: iced::advanced::Renderer, T: Window<Theme, Renderer>>
     fn title(&self) -> String {
         self.title()
     }
+
+    fn on_key_press(
+        &self,
+        key: keyboard::Key,
+        modifiers: keyboard::Modifiers,
+    ) -> Option<Box<dyn Message>> {
+        self.on_key_press(key, modifiers)
+            .map(|message| Box::new(message) as Box<dyn Message>)
+    }
 }